@@ -0,0 +1,204 @@
+use janosik_runner::runners::compiler::{Compiler, Language};
+use janosik_runner::runners::resource_control::ResourceControl;
+use janosik_runner::runners::syscall_filter::SyscallFilter;
+use janosik_runner::tasks::grader::{Grade, Grader};
+use janosik_runner::tasks::task_config::TaskConfig;
+use janosik_runner::tasks::test_suite::TestSuite;
+use janosik_runner::tasks::verdict::Verdict;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use tracing::{error, Level};
+use tracing_subscriber::FmtSubscriber;
+
+const USAGE: &str =
+    "usage: janosik-grade <task> <submissions directory> [--format csv|json] [--output <file>]";
+
+enum Format {
+    Csv,
+    Json,
+}
+
+struct Options {
+    task: String,
+    submissions: PathBuf,
+    format: Format,
+    output: Option<PathBuf>,
+}
+
+fn parse_options(args: &[String]) -> Option<Options> {
+    let mut positional = Vec::new();
+    let mut format = Format::Csv;
+    let mut output = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next()?.as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return None,
+                }
+            }
+            "--output" => output = Some(PathBuf::from(args.next()?)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    if positional.len() != 2 {
+        return None;
+    }
+
+    Some(Options {
+        task: positional[0].clone(),
+        submissions: PathBuf::from(&positional[1]),
+        format,
+        output,
+    })
+}
+
+fn grade_submission(path: &Path, suite: &TestSuite, config: &TaskConfig) -> Grade {
+    let name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    let executable = match Language::from_path(path) {
+        Some(language) => {
+            let source = match fs::read_to_string(path) {
+                Ok(source) => source,
+                Err(_) => return Grade::failed(&name, suite, Verdict::InternalError),
+            };
+
+            match Compiler::compile(&source, language, language.flags()) {
+                Ok(executable) => executable,
+                Err(e) => return Grade::failed(&name, suite, Verdict::from_error(&e)),
+            }
+        }
+        None => path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+    };
+
+    let mut grade = Grader::grade(&name, &executable, suite, config);
+
+    // Outputs of submissions are not part of the report.
+    for test in &mut grade.tests {
+        test.output.clear();
+    }
+
+    grade
+}
+
+// One row per test, with the scores of the whole submission and of every
+// subtask repeated in each of them. All submissions share the task's subtasks.
+fn to_csv(grades: &[Grade]) -> String {
+    let mut header = vec!["submission", "test", "verdict", "time_ms", "score", "max_score"]
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+
+    if let Some(grade) = grades.first() {
+        header.extend(grade.subtasks.iter().map(|s| csv_field(&s.name)));
+    }
+
+    let mut csv = format!("{}\n", header.join(","));
+
+    for grade in grades {
+        let subtasks: Vec<String> = grade
+            .subtasks
+            .iter()
+            .map(|s| format!("{:.2}", s.points))
+            .collect();
+
+        for test in &grade.tests {
+            let mut row = vec![
+                csv_field(&grade.submission),
+                csv_field(&test.test),
+                test.verdict.to_string(),
+                test.time_ms.to_string(),
+                format!("{:.2}", grade.score),
+                format!("{:.2}", grade.max_score),
+            ];
+            row.extend(subtasks.iter().cloned());
+
+            csv.push_str(&format!("{}\n", row.join(",")));
+        }
+    }
+
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn main() {
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::WARN)
+        .with_writer(std::io::stderr)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    if let Err(e) = SyscallFilter::protect_parent() {
+        error!("Cannot protect janosik-grade from the submissions: {}", e);
+        process::exit(1);
+    }
+    ResourceControl::log_mode();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_options(&args) {
+        Some(options) => options,
+        None => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let config = TaskConfig::load(&options.task);
+    let suite = match TestSuite::load(&options.task, &config) {
+        Ok(suite) => suite,
+        Err(e) => {
+            error!("Cannot load test suite: {:?}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut submissions: Vec<PathBuf> = match fs::read_dir(&options.submissions) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect(),
+        Err(e) => {
+            error!("Cannot read {:?}: {}", options.submissions, e);
+            process::exit(1);
+        }
+    };
+    submissions.sort();
+
+    let grades: Vec<Grade> = submissions
+        .iter()
+        .map(|path| grade_submission(path, &suite, &config))
+        .collect();
+
+    let report = match options.format {
+        Format::Csv => to_csv(&grades),
+        Format::Json => serde_json::to_string_pretty(&grades).unwrap(),
+    };
+
+    match options.output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, report) {
+                error!("Cannot write report to {:?}: {}", path, e);
+                process::exit(1);
+            }
+        }
+        None => println!("{}", report),
+    }
+}
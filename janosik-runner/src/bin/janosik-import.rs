@@ -0,0 +1,34 @@
+use janosik_runner::tasks::importer::Importer;
+use std::env;
+use std::path::Path;
+use std::process;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+const USAGE: &str = "usage: janosik-import <package directory or zip> <task>";
+
+fn main() {
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::WARN)
+        .with_writer(std::io::stderr)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (package, task) = match args.as_slice() {
+        [package, task] => (package, task),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    match Importer::import(Path::new(package), task) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            eprintln!("Import failed: {:?}", e);
+            process::exit(1);
+        }
+    }
+}
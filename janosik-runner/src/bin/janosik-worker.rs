@@ -0,0 +1,69 @@
+use janosik_runner::remote::connection::WorkerAddress;
+use janosik_runner::remote::worker::Worker;
+use janosik_runner::runners::process_group::ProcessGroup;
+use janosik_runner::runners::resource_control::ResourceControl;
+use janosik_runner::runners::syscall_filter::SyscallFilter;
+use std::env;
+use std::mem;
+use std::process;
+use std::ptr;
+use std::thread;
+use tracing::{error, info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+const USAGE: &str = "usage: janosik-worker <unix:<socket path> | tcp:<host>:<port>>";
+const TOKEN_VARIABLE: &str = "JANOSIK_WORKER_TOKEN";
+
+fn main() {
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::INFO)
+        .finish();
+
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+
+    let address = match env::args().nth(1).as_deref().and_then(WorkerAddress::parse) {
+        Some(address) => address,
+        None => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    handle_termination();
+    if let Err(e) = SyscallFilter::protect_parent() {
+        error!("Cannot protect the worker from its programs: {}", e);
+        process::exit(1);
+    }
+    ResourceControl::log_mode();
+
+    let token = env::var(TOKEN_VARIABLE).ok().filter(|t| !t.is_empty());
+
+    if let Err(e) = Worker::serve(&address, token) {
+        error!("Worker stopped: {}", e);
+        process::exit(1);
+    }
+}
+
+// SIGINT and SIGTERM are blocked in every thread and taken by one waiting for
+// them, so that running programs are killed before the worker exits. It has to
+// start before any other thread, which inherit the mask. Children get an empty one.
+fn handle_termination() {
+    let signals = unsafe {
+        let mut signals: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut signals);
+        libc::sigaddset(&mut signals, libc::SIGINT);
+        libc::sigaddset(&mut signals, libc::SIGTERM);
+        libc::pthread_sigmask(libc::SIG_BLOCK, &signals, ptr::null_mut());
+        signals
+    };
+
+    thread::spawn(move || {
+        let mut signal = 0;
+
+        if unsafe { libc::sigwait(&signals, &mut signal) } == 0 {
+            info!("Received signal {}, killing running programs", signal);
+            ProcessGroup::kill_all();
+            process::exit(0);
+        }
+    });
+}
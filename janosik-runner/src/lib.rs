@@ -0,0 +1,10 @@
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod runners;
+pub mod tasks;
+
+pub use runners::binary_runner::Execution;
+pub use runners::resource_usage::ResourceUsage;
+pub use runners::run_request::{RunRequest, RunResult};
+pub use runners::runner_error::RunnerError;
+pub use runners::scratch_directory::NamedFile;
@@ -0,0 +1,96 @@
+use std::io;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkerAddress {
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl WorkerAddress {
+    // Accepts `unix:<path>` and `tcp:<host>:<port>`.
+    pub fn parse(address: &str) -> Option<WorkerAddress> {
+        if let Some(path) = address.strip_prefix("unix:") {
+            return Some(WorkerAddress::Unix(PathBuf::from(path)));
+        }
+
+        address
+            .strip_prefix("tcp:")
+            .map(|host| WorkerAddress::Tcp(host.to_string()))
+    }
+
+    pub fn connect(&self, timeout: Duration) -> io::Result<Connection> {
+        match self {
+            WorkerAddress::Unix(path) => {
+                let stream = UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Ok(Connection::Unix(stream))
+            }
+            WorkerAddress::Tcp(host) => {
+                let address = host.to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "cannot resolve worker address")
+                })?;
+                let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Ok(Connection::Tcp(stream))
+            }
+        }
+    }
+}
+
+pub enum Connection {
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Unix(stream) => stream.read(buf),
+            Connection::Tcp(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Connection::Unix(stream) => stream.write(buf),
+            Connection::Tcp(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Connection::Unix(stream) => stream.flush(),
+            Connection::Tcp(stream) => stream.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::remote::connection::WorkerAddress;
+    use std::path::PathBuf;
+
+    #[test]
+    fn should_parse_addresses() {
+        assert_eq!(
+            WorkerAddress::parse("unix:/tmp/worker.sock"),
+            Some(WorkerAddress::Unix(PathBuf::from("/tmp/worker.sock")))
+        );
+        assert_eq!(
+            WorkerAddress::parse("tcp:127.0.0.1:7070"),
+            Some(WorkerAddress::Tcp("127.0.0.1:7070".to_string()))
+        );
+        assert_eq!(WorkerAddress::parse("127.0.0.1:7070"), None);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod connection;
+pub mod protocol;
+pub mod worker;
+pub mod worker_pool;
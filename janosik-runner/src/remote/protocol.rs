@@ -0,0 +1,163 @@
+//! Runner worker protocol.
+//!
+//! Every connection carries exactly one exchange: the client writes a single
+//! [`Request`] serialized as JSON and terminated by a newline, the worker answers
+//! with a single newline-terminated JSON [`Response`] and closes the connection.
+//! Messages longer than [`MAX_MESSAGE_BYTES`] are rejected.
+//!
+//! `version` must be equal to [`PROTOCOL_VERSION`] on both sides. A worker
+//! receiving any other version answers with [`Reply::UnsupportedVersion`]
+//! carrying the version it speaks. Incompatible changes to the messages below
+//! bump the version.
+//!
+//! A worker started with a token answers only requests carrying the same
+//! `token`, anything else gets [`Reply::Unauthorized`].
+//!
+//! ```text
+//! > {"version":2,"token":"secret","command":"Ping"}
+//! < {"version":2,"reply":"Pong"}
+//! > {"version":2,"token":"secret","command":{"Execute":{"Run":{"program":"sum","input":"1 2","files":[]}}}}
+//! < {"version":2,"reply":{"Finished":{"Ok":{"Executed":{"output":"3\n","files":[],"usage":{...}}}}}}
+//! ```
+
+use crate::runners::job::{Job, JobResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+
+pub const PROTOCOL_VERSION: u32 = 2;
+pub const MAX_MESSAGE_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub enum Command {
+    /// Health check, answered with [`Reply::Pong`].
+    Ping,
+    /// Runs the job with `bin` and `tasks` of the worker, answered with [`Reply::Finished`].
+    Execute(Job),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Reply {
+    Pong,
+    Finished(JobResult),
+    UnsupportedVersion(u32),
+    Unauthorized,
+    InvalidRequest(String),
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Request {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub command: Command,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Response {
+    pub version: u32,
+    pub reply: Reply,
+}
+
+impl Request {
+    pub fn new(command: Command, token: Option<&str>) -> Request {
+        Request {
+            version: PROTOCOL_VERSION,
+            token: token.map(str::to_string),
+            command,
+        }
+    }
+
+    // Compares every byte, so that the time taken does not tell how much
+    // of a guessed token was right.
+    pub fn is_authorized(&self, token: Option<&str>) -> bool {
+        let expected = match token {
+            Some(expected) => expected.as_bytes(),
+            None => return true,
+        };
+        let given = self.token.as_deref().unwrap_or("").as_bytes();
+
+        given.len() == expected.len()
+            && given
+                .iter()
+                .zip(expected)
+                .fold(0, |difference, (a, b)| difference | (a ^ b))
+                == 0
+    }
+}
+
+impl Response {
+    pub fn new(reply: Reply) -> Response {
+        Response {
+            version: PROTOCOL_VERSION,
+            reply,
+        }
+    }
+}
+
+pub fn write_message<T: Serialize, W: Write>(writer: &mut W, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+
+    writer.write_all(&line)?;
+    writer.flush()
+}
+
+pub fn read_message<T: DeserializeOwned, R: Read>(reader: R) -> io::Result<T> {
+    let mut line = String::new();
+    BufReader::new(reader.take(MAX_MESSAGE_BYTES + 1)).read_line(&mut line)?;
+
+    if line.len() as u64 > MAX_MESSAGE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message too long",
+        ));
+    }
+
+    serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::remote::protocol::{
+        read_message, write_message, Command, Request, PROTOCOL_VERSION,
+    };
+    use crate::runners::job::Job;
+    use crate::runners::run_request::RunRequest;
+
+    #[test]
+    fn should_round_trip_request() {
+        let job = Job::Run(RunRequest::new("sum", "1 2"));
+        let request = Request::new(Command::Execute(job), Some("secret"));
+        let mut buffer = Vec::new();
+
+        write_message(&mut buffer, &request).unwrap();
+
+        assert!(buffer.ends_with(b"\n"));
+        assert_eq!(
+            read_message::<Request, _>(buffer.as_slice()).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn should_serialize_ping() {
+        let json = serde_json::to_string(&Request::new(Command::Ping, None)).unwrap();
+
+        assert_eq!(
+            json,
+            format!(r#"{{"version":{},"command":"Ping"}}"#, PROTOCOL_VERSION)
+        );
+    }
+
+    #[test]
+    fn should_check_token() {
+        let request = Request::new(Command::Ping, Some("secret"));
+
+        assert!(request.is_authorized(Some("secret")));
+        assert!(request.is_authorized(None));
+        assert!(!request.is_authorized(Some("secrets")));
+        assert!(!Request::new(Command::Ping, None).is_authorized(Some("secret")));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::remote::connection::WorkerAddress;
+use crate::remote::protocol::{
+    read_message, write_message, Command, Reply, Request, Response, PROTOCOL_VERSION,
+};
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_CONNECTIONS: usize = 32;
+
+trait Stream: Read + Write + Send + 'static {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()>;
+}
+
+impl Stream for TcpStream {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+impl Stream for UnixStream {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+// Counts connections being handled, accepting waits while all are taken.
+struct Slots {
+    taken: Mutex<usize>,
+    freed: Condvar,
+    limit: usize,
+}
+
+struct Slot(Arc<Slots>);
+
+impl Slots {
+    fn acquire(slots: &Arc<Slots>) -> Slot {
+        let mut taken = slots.taken.lock().unwrap();
+        while *taken >= slots.limit {
+            taken = slots.freed.wait(taken).unwrap();
+        }
+        *taken += 1;
+
+        Slot(Arc::clone(slots))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        *self.0.taken.lock().unwrap() -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+pub struct Worker {}
+
+impl Worker {
+    // Requests must carry `token` when one is given. Without it anybody able to
+    // connect runs code on the worker, so TCP is then only served on loopback.
+    pub fn serve(address: &WorkerAddress, token: Option<String>) -> io::Result<()> {
+        match address {
+            WorkerAddress::Unix(path) => {
+                if path.exists() {
+                    fs::remove_file(path)?;
+                }
+
+                let listener = UnixListener::bind(path)?;
+                info!("Worker listening on {:?}", path);
+                Worker::accept(listener.incoming(), token, REQUEST_TIMEOUT, MAX_CONNECTIONS)
+            }
+            WorkerAddress::Tcp(host) => {
+                let listener = TcpListener::bind(host)?;
+                Worker::serve_tcp(listener, token)
+            }
+        }
+    }
+
+    pub fn serve_tcp(listener: TcpListener, token: Option<String>) -> io::Result<()> {
+        let address = listener.local_addr()?;
+
+        if token.is_none() && !address.ip().is_loopback() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "serving TCP outside loopback requires JANOSIK_WORKER_TOKEN",
+            ));
+        }
+
+        info!("Worker listening on {:?}", address);
+        Worker::accept(listener.incoming(), token, REQUEST_TIMEOUT, MAX_CONNECTIONS)
+    }
+
+    // Connections are read before the token is checked, so a silent client is
+    // dropped after `timeout` and at most `max_connections` are handled at once.
+    fn accept<S, I>(
+        incoming: I,
+        token: Option<String>,
+        timeout: Duration,
+        max_connections: usize,
+    ) -> io::Result<()>
+    where
+        S: Stream,
+        I: Iterator<Item = io::Result<S>>,
+    {
+        let token = Arc::new(token);
+        let slots = Arc::new(Slots {
+            taken: Mutex::new(0),
+            freed: Condvar::new(),
+            limit: max_connections,
+        });
+
+        for stream in incoming {
+            let stream = match stream.and_then(|s| s.set_timeouts(timeout).map(|_| s)) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("Error accepting connection: {}", e);
+                    continue;
+                }
+            };
+
+            let slot = Slots::acquire(&slots);
+            let token = Arc::clone(&token);
+            thread::spawn(move || {
+                Worker::handle(stream, token.as_deref());
+                drop(slot);
+            });
+        }
+
+        Ok(())
+    }
+
+    fn handle<S: Read + Write>(mut stream: S, token: Option<&str>) {
+        let reply = match read_message::<Request, _>(&mut stream) {
+            Ok(request) if request.version != PROTOCOL_VERSION => {
+                warn!("Unsupported protocol version {}", request.version);
+                Reply::UnsupportedVersion(PROTOCOL_VERSION)
+            }
+            Ok(request) if !request.is_authorized(token) => {
+                warn!("Request with invalid token");
+                Reply::Unauthorized
+            }
+            Ok(request) => Worker::execute(request.command),
+            Err(e) => Reply::InvalidRequest(e.to_string()),
+        };
+
+        if let Err(e) = write_message(&mut stream, &Response::new(reply)) {
+            error!("Error sending response: {}", e);
+        }
+    }
+
+    fn execute(command: Command) -> Reply {
+        match command {
+            Command::Ping => Reply::Pong,
+            Command::Execute(job) => {
+                info!("Executing a job for a remote client");
+                Reply::Finished(job.execute())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::remote::protocol::{read_message, write_message, Command, Reply, Request, Response};
+    use crate::remote::worker::Worker;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn should_drop_silent_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            Worker::accept(listener.incoming(), None, Duration::from_millis(200), 1)
+        });
+
+        let mut silent = TcpStream::connect(address).unwrap();
+        let mut client = TcpStream::connect(address).unwrap();
+        write_message(&mut client, &Request::new(Command::Ping, None)).unwrap();
+
+        let response: Response = read_message(&mut client).unwrap();
+        assert!(matches!(response.reply, Reply::Pong));
+
+        let response: Response = read_message(&mut silent).unwrap();
+        assert!(matches!(response.reply, Reply::InvalidRequest(_)));
+    }
+}
@@ -0,0 +1,258 @@
+use crate::remote::connection::{Connection, WorkerAddress};
+use crate::remote::protocol::{read_message, write_message, Command, Reply, Request, Response};
+use crate::runners::job::{Job, JobResult};
+use crate::runners::runner_error::RunnerError;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+struct WorkerState {
+    address: WorkerAddress,
+    healthy: AtomicBool,
+}
+
+// Dispatches jobs to remote workers in turn, skipping the ones that failed
+// until a health check finds them alive again.
+pub struct WorkerPool {
+    workers: Vec<WorkerState>,
+    next: AtomicUsize,
+    token: Option<String>,
+}
+
+impl WorkerPool {
+    pub fn new(addresses: Vec<WorkerAddress>, token: Option<String>) -> WorkerPool {
+        let workers = addresses
+            .into_iter()
+            .map(|address| WorkerState {
+                address,
+                healthy: AtomicBool::new(true),
+            })
+            .collect();
+
+        WorkerPool {
+            workers,
+            next: AtomicUsize::new(0),
+            token,
+        }
+    }
+
+    // Comma separated list of worker addresses, see `WorkerAddress::parse`.
+    pub fn from_list(list: &str, token: Option<String>) -> Option<WorkerPool> {
+        let addresses: Vec<WorkerAddress> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .filter_map(|a| {
+                let address = WorkerAddress::parse(a);
+                if address.is_none() {
+                    warn!("Invalid worker address '{}'", a);
+                }
+                address
+            })
+            .collect();
+
+        if addresses.is_empty() {
+            return None;
+        }
+
+        Some(WorkerPool::new(addresses, token))
+    }
+
+    pub fn healthy_workers(&self) -> usize {
+        self.workers
+            .iter()
+            .filter(|w| w.healthy.load(Ordering::Relaxed))
+            .count()
+    }
+
+    pub fn check_health(&self) {
+        for worker in &self.workers {
+            let alive = matches!(
+                self.send(&worker.address, Command::Ping, PING_TIMEOUT)
+                    .and_then(|mut connection| read_message::<Response, _>(&mut connection)),
+                Ok(Response {
+                    reply: Reply::Pong,
+                    ..
+                })
+            );
+
+            if alive != worker.healthy.swap(alive, Ordering::Relaxed) {
+                info!("Worker {:?} is now healthy: {}", worker.address, alive);
+            }
+        }
+    }
+
+    pub fn start_health_checks(pool: Arc<WorkerPool>) {
+        thread::spawn(move || loop {
+            pool.check_health();
+            thread::sleep(HEALTH_CHECK_INTERVAL);
+        });
+    }
+
+    pub fn execute(&self, job: &Job) -> JobResult {
+        self.execute_with_timeout(job, job.timeout())
+    }
+
+    // Workers that cannot be reached or stop answering are skipped, but a job
+    // that is still running when the timeout passes is not sent to another
+    // one, it would most likely time out there too.
+    fn execute_with_timeout(&self, job: &Job, timeout: Duration) -> JobResult {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        for i in 0..self.workers.len() {
+            let worker = &self.workers[(start + i) % self.workers.len()];
+
+            if !worker.healthy.load(Ordering::Relaxed) {
+                continue;
+            }
+
+            let response = self
+                .send(&worker.address, Command::Execute(job.clone()), timeout)
+                .map(|mut connection| read_message::<Response, _>(&mut connection));
+
+            match response {
+                Ok(Ok(Response {
+                    reply: Reply::Finished(result),
+                    ..
+                })) => return result,
+                Ok(Err(e)) if is_timeout(&e) => {
+                    warn!("Worker {:?} timed out", worker.address);
+                    return Err(RunnerError::Timeout);
+                }
+                Ok(Ok(response)) => warn!("Worker {:?} replied {:?}", worker.address, response),
+                Ok(Err(e)) | Err(e) => warn!("Worker {:?} failed: {}", worker.address, e),
+            }
+
+            worker.healthy.store(false, Ordering::Relaxed);
+        }
+
+        Err(RunnerError::Other("no runner worker available".to_string()))
+    }
+
+    fn send(
+        &self,
+        address: &WorkerAddress,
+        command: Command,
+        timeout: Duration,
+    ) -> io::Result<Connection> {
+        let mut connection = address.connect(timeout)?;
+
+        write_message(
+            &mut connection,
+            &Request::new(command, self.token.as_deref()),
+        )?;
+
+        Ok(connection)
+    }
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::remote::connection::WorkerAddress;
+    use crate::remote::worker::Worker;
+    use crate::remote::worker_pool::WorkerPool;
+    use crate::runners::job::Job;
+    use crate::runners::run_request::RunRequest;
+    use crate::runners::runner_error::RunnerError;
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    fn start_worker(token: Option<&str>) -> WorkerAddress {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = WorkerAddress::Tcp(listener.local_addr().unwrap().to_string());
+        let token = token.map(str::to_string);
+
+        thread::spawn(move || Worker::serve_tcp(listener, token));
+        address
+    }
+
+    // Accepts connections and never answers, like a worker still running a job.
+    fn busy_worker() -> WorkerAddress {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = WorkerAddress::Tcp(listener.local_addr().unwrap().to_string());
+
+        thread::spawn(move || {
+            let _streams: Vec<_> = listener.incoming().collect();
+        });
+        address
+    }
+
+    fn job() -> Job {
+        Job::Run(RunRequest::new("not found", "1 2"))
+    }
+
+    fn dead_address() -> WorkerAddress {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        WorkerAddress::Tcp(listener.local_addr().unwrap().to_string())
+    }
+
+    #[test]
+    fn should_mark_dead_workers_unhealthy() {
+        let pool = WorkerPool::new(vec![start_worker(None), dead_address()], None);
+
+        pool.check_health();
+
+        assert_eq!(pool.healthy_workers(), 1);
+    }
+
+    #[test]
+    fn should_fail_over_to_next_worker() {
+        let pool = WorkerPool::new(vec![dead_address(), start_worker(None)], None);
+
+        let result = pool.execute(&job());
+
+        assert_eq!(result.unwrap_err(), RunnerError::NotFound);
+        assert_eq!(pool.healthy_workers(), 1);
+    }
+
+    #[test]
+    fn should_fail_without_workers() {
+        let pool = WorkerPool::new(vec![dead_address()], None);
+
+        assert!(pool.execute(&job()).is_err());
+    }
+
+    #[test]
+    fn should_keep_timeout_as_result() {
+        let pool = WorkerPool::new(vec![busy_worker(), start_worker(None)], None);
+
+        let result = pool.execute_with_timeout(&job(), Duration::from_millis(200));
+
+        assert_eq!(result.unwrap_err(), RunnerError::Timeout);
+        assert_eq!(pool.healthy_workers(), 2);
+    }
+
+    #[test]
+    fn should_reject_invalid_token() {
+        let address = start_worker(Some("secret"));
+        let pool = WorkerPool::new(vec![address.clone()], Some("wrong".to_string()));
+
+        pool.check_health();
+        assert_eq!(pool.healthy_workers(), 0);
+
+        let pool = WorkerPool::new(vec![address], Some("secret".to_string()));
+
+        assert_eq!(pool.execute(&job()).unwrap_err(), RunnerError::NotFound);
+    }
+
+    #[test]
+    fn should_require_token_outside_loopback() {
+        let listener = TcpListener::bind("0.0.0.0:0").unwrap();
+
+        assert!(Worker::serve_tcp(listener, None).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::runner_error::RunnerError;
+use crate::runners::validator::Validator;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::info;
+
+pub const WARMUP_RUNS: usize = 2;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &[Duration]) -> Option<Statistics> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Statistics {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BenchmarkResult {
+    pub runs: usize,
+    pub wall_time: Statistics,
+    pub cpu_time: Statistics,
+}
+
+pub struct Benchmark {}
+
+impl Benchmark {
+    pub fn run(
+        program_name: &str,
+        input: &str,
+        runs: usize,
+    ) -> Result<BenchmarkResult, RunnerError> {
+        if input.is_empty() {
+            return Err(RunnerError::NoInput);
+        }
+
+        Validator::validate(program_name, input)?;
+
+        let mut wall_times = Vec::new();
+        let mut cpu_times = Vec::new();
+
+        for i in 0..WARMUP_RUNS + runs {
+            let execution = BinaryRunner::execute_measured(program_name, &[], input)?;
+
+            if i >= WARMUP_RUNS {
+                wall_times.push(execution.usage.wall_time);
+                cpu_times.push(execution.usage.cpu_time);
+            }
+        }
+
+        info!("Benchmarked {} with {} runs", program_name, runs);
+
+        let error = || RunnerError::Other("no benchmark runs".to_string());
+        Ok(BenchmarkResult {
+            runs,
+            wall_time: Statistics::from_samples(&wall_times).ok_or_else(error)?,
+            cpu_time: Statistics::from_samples(&cpu_times).ok_or_else(error)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::benchmark::Statistics;
+    use std::time::Duration;
+
+    #[test]
+    fn should_compute_statistics() {
+        let samples: Vec<Duration> = [4, 2, 8, 6]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect();
+
+        let statistics = Statistics::from_samples(&samples).unwrap();
+
+        assert_eq!(statistics.min, Duration::from_millis(2));
+        assert_eq!(statistics.median, Duration::from_millis(5));
+        assert_eq!(statistics.mean.as_millis(), 5);
+        assert_eq!(statistics.stddev.as_micros(), 2581);
+    }
+
+    #[test]
+    fn should_return_none_without_samples() {
+        assert_eq!(Statistics::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::runners::digest::sha256_hex;
+use crate::runners::process_group::ProcessGroup;
+use crate::runners::resource_control::{ResourceLimits, RESOURCE_CONTROL};
+use crate::runners::resource_usage::ResourceUsage;
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::{Environment, NamedFile, ScratchDirectory};
+use crate::runners::syscall_filter::{SyscallFilter, SyscallPolicy};
+use crate::runners::validator::Validator;
+use crate::tasks::task_config::TaskConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, Error, ErrorKind, Read};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(30);
+// Stays well below the time the bot waits for a worker to answer.
+pub const MAX_TIME_LIMIT: Duration = Duration::from_secs(60);
+
+type RunnerResult = Result<String, RunnerError>;
+type ExecutionResult = Result<Execution, RunnerError>;
+type ProcessResult = Result<ProcessOutput, RunnerError>;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Execution {
+    pub output: String,
+    pub files: Vec<NamedFile>,
+    pub usage: ResourceUsage,
+}
+
+#[derive(Debug)]
+pub struct ProcessOutput {
+    pub status_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+    pub files: Vec<NamedFile>,
+    pub usage: ResourceUsage,
+}
+
+pub struct ProcessOptions {
+    pub environment: Environment,
+    pub input_files: Vec<NamedFile>,
+    pub output_files: Vec<String>,
+    pub time_limit: Duration,
+    pub limits: ResourceLimits,
+    pub syscalls: SyscallPolicy,
+}
+
+impl Default for ProcessOptions {
+    fn default() -> Self {
+        ProcessOptions {
+            environment: Environment::new(),
+            input_files: Vec::new(),
+            output_files: Vec::new(),
+            time_limit: DEFAULT_TIME_LIMIT,
+            limits: ResourceLimits::default(),
+            syscalls: SyscallPolicy::Unrestricted,
+        }
+    }
+}
+
+impl ProcessOptions {
+    pub fn for_task(config: &TaskConfig) -> ProcessOptions {
+        ProcessOptions {
+            environment: config.environment.clone(),
+            output_files: config.output_files.clone(),
+            time_limit: config.time_limit(),
+            limits: config.resource_limits(),
+            syscalls: config.reference_syscalls(),
+            ..ProcessOptions::default()
+        }
+    }
+
+    pub fn for_submission(config: &TaskConfig) -> ProcessOptions {
+        ProcessOptions {
+            syscalls: config.submission_syscalls(),
+            ..ProcessOptions::for_task(config)
+        }
+    }
+}
+
+pub struct BinaryRunner {}
+
+impl BinaryRunner {
+    pub fn run(program_name: &str, input: &str) -> RunnerResult {
+        BinaryRunner::run_measured(program_name, input).map(|e| e.output)
+    }
+
+    pub fn run_measured(program_name: &str, input: &str) -> ExecutionResult {
+        BinaryRunner::run_with_files(program_name, input, &[], None)
+    }
+
+    // `time_limit` replaces the limit of the program's task when given.
+    pub fn run_with_files(
+        program_name: &str,
+        input: &str,
+        files: &[NamedFile],
+        time_limit: Option<Duration>,
+    ) -> ExecutionResult {
+        if input.is_empty() && files.is_empty() {
+            return Err(RunnerError::NoInput);
+        }
+
+        if !input.is_empty() {
+            Validator::validate(program_name, input)?;
+        }
+
+        BinaryRunner::execute_with_files(program_name, &[], input, files, time_limit)
+    }
+
+    pub fn execute(program_name: &str, args: &[String], input: &str) -> RunnerResult {
+        BinaryRunner::execute_measured(program_name, args, input).map(|e| e.output)
+    }
+
+    pub fn execute_measured(program_name: &str, args: &[String], input: &str) -> ExecutionResult {
+        BinaryRunner::execute_with_files(program_name, args, input, &[], None)
+    }
+
+    fn execute_with_files(
+        program_name: &str,
+        args: &[String],
+        input: &str,
+        files: &[NamedFile],
+        time_limit: Option<Duration>,
+    ) -> ExecutionResult {
+        let mut command = Command::new(
+            BinaryRunner::binary_path(program_name).map_err(|_| RunnerError::NotFound)?,
+        );
+        command.args(args);
+
+        let config = TaskConfig::load(program_name);
+        let options = ProcessOptions {
+            input_files: files.to_vec(),
+            time_limit: time_limit
+                .unwrap_or_else(|| config.time_limit())
+                .min(MAX_TIME_LIMIT),
+            ..ProcessOptions::for_task(&config)
+        };
+
+        info!("Program {} started", program_name);
+        let process = BinaryRunner::execute_command_with(&mut command, input, &options)?;
+        info!(
+            "{} returned {:?} ({:?})",
+            program_name, process.status_code, process.usage
+        );
+
+        if process.status_code != 0 {
+            error!("{} crashed!", program_name);
+            return Err(RunnerError::Crash);
+        }
+
+        if process.stdout.is_empty() && process.files.is_empty() {
+            return Err(RunnerError::NoOutput);
+        }
+
+        Ok(Execution {
+            output: process.stdout,
+            files: process.files,
+            usage: process.usage,
+        })
+    }
+
+    pub fn execute_command(
+        command: &mut Command,
+        input: &str,
+        environment: &Environment,
+    ) -> ProcessResult {
+        let options = ProcessOptions {
+            environment: environment.clone(),
+            ..ProcessOptions::default()
+        };
+
+        BinaryRunner::execute_command_with(command, input, &options)
+    }
+
+    pub fn execute_command_with(
+        command: &mut Command,
+        input: &str,
+        options: &ProcessOptions,
+    ) -> ProcessResult {
+        let scratch = ScratchDirectory::create()?;
+        let file = BinaryRunner::create_input_file(&scratch, input)?;
+        scratch.write_files(&options.input_files)?;
+        let cgroup = RESOURCE_CONTROL.confine(command, &options.limits, options.time_limit)?;
+        let command = ProcessGroup::isolate(scratch.prepare(command, &options.environment));
+        let mut process = SyscallFilter::apply(command, options.syscalls)
+            .stdin(Stdio::from(file))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| RunnerError::Other(e.to_string()))?;
+        let group = ProcessGroup::track(&process);
+        let start = Instant::now();
+
+        let stdout = BinaryRunner::read_pipe(process.stdout.take());
+        let stderr = BinaryRunner::read_pipe(process.stderr.take());
+
+        let waited = BinaryRunner::wait_timeout(&mut process, start, options.time_limit);
+        // Leftover children would keep the pipes open and the readers waiting.
+        drop(group);
+        let (status_code, mut usage) = waited?;
+
+        // The cgroup also accounts for everything the program forked.
+        if let Some(cgroup) = &cgroup {
+            if cgroup.oom_killed() {
+                return Err(RunnerError::MemoryLimitExceeded);
+            }
+
+            usage.max_rss_kb = cgroup.peak_memory_kb().unwrap_or(usage.max_rss_kb);
+            usage.cpu_time = cgroup.cpu_time().unwrap_or(usage.cpu_time);
+        }
+
+        let read_error = |_| RunnerError::Other("Could not read program output.".to_string());
+        Ok(ProcessOutput {
+            status_code,
+            stdout: stdout.join().map_err(read_error)?,
+            stderr: stderr.join().map_err(read_error)?,
+            files: scratch.collect_files(&options.output_files),
+            usage,
+        })
+    }
+
+    fn create_input_file(scratch: &ScratchDirectory, input: &str) -> Result<File, RunnerError> {
+        let path = scratch.write(".stdin", input)?;
+
+        File::open(path).map_err(|_| RunnerError::Other("cannot open input file".to_string()))
+    }
+
+    pub fn binary_path(program_name: &str) -> Result<PathBuf, Error> {
+        if program_name.contains("..") {
+            return Err(Error::new(ErrorKind::NotFound, "invalid path"));
+        }
+
+        let path = PathBuf::from("bin/").canonicalize()?;
+        let program_path = path.join(Path::new(program_name)).canonicalize()?;
+
+        let err = || Error::new(ErrorKind::NotFound, "invalid path");
+        let path_str = program_path.to_str().ok_or(err())?;
+        if !path_str.starts_with(path.to_str().ok_or(err())?) {
+            return Err(err());
+        }
+
+        Ok(program_path)
+    }
+
+    pub fn binary_hash(program_name: &str) -> Option<String> {
+        let binary = fs::read(BinaryRunner::binary_path(program_name).ok()?).ok()?;
+
+        Some(sha256_hex(&binary))
+    }
+
+    fn wait_timeout(
+        child: &mut Child,
+        start: Instant,
+        timeout: Duration,
+    ) -> Result<(i32, ResourceUsage), RunnerError> {
+        let pid = child.id() as libc::pid_t;
+
+        loop {
+            let mut status = 0;
+            let mut rusage: libc::rusage = unsafe { mem::zeroed() };
+
+            // wait4 reaps the child and reports its resource usage in one call,
+            // which std::process::Child has no way to give us.
+            match unsafe { libc::wait4(pid, &mut status, libc::WNOHANG, &mut rusage) } {
+                0 => {}
+                r if r == pid => {
+                    let usage = ResourceUsage::from_rusage(&rusage, start.elapsed());
+                    if libc::WIFSIGNALED(status) && libc::WTERMSIG(status) == libc::SIGSYS {
+                        return Err(RunnerError::ForbiddenSyscall);
+                    }
+                    if libc::WIFSIGNALED(status) && libc::WTERMSIG(status) == libc::SIGXCPU {
+                        return Err(RunnerError::Timeout);
+                    }
+
+                    let code = if libc::WIFEXITED(status) {
+                        libc::WEXITSTATUS(status)
+                    } else {
+                        -1
+                    };
+
+                    return Ok((code, usage));
+                }
+                _ => return Err(RunnerError::Other("cannot wait for program".to_string())),
+            }
+
+            if start.elapsed() >= timeout {
+                unsafe {
+                    libc::kill(-pid, libc::SIGKILL);
+                }
+                let _ = child.wait();
+                return Err(RunnerError::Timeout);
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+        thread::spawn(move || {
+            let mut output = Vec::new();
+
+            if let Some(pipe) = pipe {
+                let _ = BufReader::new(pipe).read_to_end(&mut output);
+            }
+
+            String::from_utf8_lossy(&output).to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::binary_runner::{BinaryRunner, ProcessOptions};
+    use crate::runners::runner_error::RunnerError;
+    use crate::runners::scratch_directory::ScratchDirectory;
+    use std::fs;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn should_return_error_on_empty_input() {
+        let out = BinaryRunner::run("not found", "");
+
+        assert!(out.is_err());
+        assert_eq!(out.unwrap_err(), RunnerError::NoInput);
+    }
+
+    #[test]
+    fn should_not_wait_for_background_processes() {
+        let start = Instant::now();
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("sleep 30 & echo done");
+
+        let out = BinaryRunner::execute_command_with(&mut command, "", &ProcessOptions::default());
+
+        assert_eq!(out.unwrap().stdout, "done\n");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn should_kill_forked_processes_on_timeout() {
+        let pids = ScratchDirectory::create().unwrap();
+        let pid_file = pids.path().join("forked");
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg("sleep 30 & echo $! > \"$1\"; sleep 30")
+            .arg("sh")
+            .arg(&pid_file);
+        let options = ProcessOptions {
+            time_limit: Duration::from_millis(100),
+            ..ProcessOptions::default()
+        };
+
+        let out = BinaryRunner::execute_command_with(&mut command, "", &options);
+        let forked: libc::pid_t = fs::read_to_string(&pid_file)
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        // Killed but not reaped yet if nothing reaps orphans.
+        let zombie = fs::read_to_string(format!("/proc/{}/stat", forked))
+            .map(|stat| stat.contains(") Z "))
+            .unwrap_or(false);
+
+        assert_eq!(out.unwrap_err(), RunnerError::Timeout);
+        assert!(unsafe { libc::kill(forked, 0) } != 0 || zombie);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::digest::sha256_hex;
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::{Environment, ScratchDirectory};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, DirBuilder};
+use std::io::ErrorKind;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{error, info};
+
+const CACHE_DIRECTORY: &str = "janosik-builds";
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Language {
+    C,
+    Cpp,
+}
+
+impl Language {
+    pub fn from_tag(tag: &str) -> Language {
+        match tag {
+            "c" => Language::C,
+            _ => Language::Cpp,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Language> {
+        match path.extension()?.to_str()? {
+            "c" => Some(Language::C),
+            "cpp" | "cc" | "cxx" => Some(Language::Cpp),
+            _ => None,
+        }
+    }
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Language::C => "c",
+            Language::Cpp => "cpp",
+        }
+    }
+
+    // Flags of submissions and reference solutions, the same everywhere
+    // they are compiled.
+    pub fn flags(self) -> &'static [&'static str] {
+        match self {
+            Language::C => &["-O2"],
+            Language::Cpp => &["-O2", "-std=c++17"],
+        }
+    }
+
+    fn compiler(self) -> &'static str {
+        match self {
+            Language::C => "gcc",
+            Language::Cpp => "g++",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Language::C => "c",
+            Language::Cpp => "cpp",
+        }
+    }
+}
+
+pub struct Compiler {}
+
+impl Compiler {
+    pub fn compile(
+        source: &str,
+        language: Language,
+        flags: &[&str],
+    ) -> Result<PathBuf, RunnerError> {
+        let binary_path =
+            Compiler::cache_directory()?.join(Compiler::build_key(source, language, flags));
+
+        if binary_path.exists() {
+            info!("Using cached build {:?}", binary_path);
+            return Ok(binary_path);
+        }
+
+        // Only a finished binary is moved into the cache, so a concurrent
+        // compilation of the same source never runs a half-written one.
+        let build = ScratchDirectory::create()?;
+        let source_path = build.write(&format!("main.{}", language.extension()), source)?;
+        let built_path = build.path().join("main");
+
+        info!("Compiling {:?} with {:?}", source_path, flags);
+
+        let mut command = Command::new(language.compiler());
+        command
+            .args(flags)
+            .arg("-o")
+            .arg(&built_path)
+            .arg(&source_path);
+
+        let process = BinaryRunner::execute_command(&mut command, "", &Environment::new())?;
+
+        if process.status_code != 0 {
+            error!("Compilation failed");
+            let directory = build.path().to_string_lossy().to_string();
+            return Err(RunnerError::CompilationError(
+                process.stderr.replace(&format!("{}/", directory), ""),
+            ));
+        }
+
+        if fs::rename(&built_path, &binary_path).is_err() {
+            return Err(RunnerError::Other("cannot cache binary".to_string()));
+        }
+
+        Ok(binary_path)
+    }
+
+    fn build_key(source: &str, language: Language, flags: &[&str]) -> String {
+        let key = [&[source, language.extension()], flags].concat().join("\0");
+
+        sha256_hex(key.as_bytes())
+    }
+
+    // Cached binaries are run without compiling them again, so the cache
+    // must belong to the bot and nobody else may write to it.
+    fn cache_directory() -> Result<PathBuf, RunnerError> {
+        let uid = unsafe { libc::geteuid() };
+        let path = env::temp_dir().join(format!("{}-{}", CACHE_DIRECTORY, uid));
+
+        match DirBuilder::new().mode(0o700).create(&path) {
+            Err(e) if e.kind() != ErrorKind::AlreadyExists => {
+                return Err(RunnerError::Other("cannot create build cache".to_string()));
+            }
+            _ => {}
+        }
+
+        let private = fs::symlink_metadata(&path)
+            .map(|m| m.is_dir() && m.uid() == uid && m.mode() & 0o077 == 0)
+            .unwrap_or(false);
+
+        if !private {
+            error!("Build cache {:?} is not private", path);
+            return Err(RunnerError::Other("build cache is not private".to_string()));
+        }
+
+        Ok(path)
+    }
+}
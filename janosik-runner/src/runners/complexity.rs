@@ -0,0 +1,341 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::generator::Generator;
+use crate::runners::runner_error::RunnerError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use tracing::info;
+
+const FIRST_SIZE: u32 = 100;
+const MAX_POINTS: u32 = 10;
+const TIME_BUDGET: Duration = Duration::from_secs(1);
+const SEED: u64 = 0;
+// Exponential programs outgrow the budget long before the first doubled
+// size, so the sizes grow linearly up to it first.
+const LINEAR_STEP: u32 = 3;
+const LINEAR_END: u32 = 60;
+// Runs of an exponential program only this many times slower than its
+// fastest one show the growth, the faster ones are mostly its start-up.
+const GROWTH_FACTOR: f64 = 2.0;
+const MIN_TIME: f64 = 1e-4;
+const MIN_POINTS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+            Complexity::Exponential => "O(2ⁿ)",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl Complexity {
+    // 2ⁿ of the doubled sizes does not fit in an f64, the exponential fit is
+    // done in logarithms instead.
+    const POLYNOMIAL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+    ];
+
+    pub fn value(self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 0.0,
+            Complexity::Logarithmic => n.log2(),
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * n.log2(),
+            Complexity::Quadratic => n * n,
+            Complexity::Cubic => n * n * n,
+            Complexity::Exponential => n.exp2(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Fit {
+    pub complexity: Complexity,
+    pub offset: f64,
+    pub factor: f64,
+    pub error: f64,
+}
+
+impl Fit {
+    pub fn predict(&self, n: f64) -> f64 {
+        match self.complexity {
+            Complexity::Exponential => (self.offset + self.factor * n).exp(),
+            complexity => self.offset + self.factor * complexity.value(n),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Measurement {
+    pub size: u32,
+    pub time: Duration,
+}
+
+pub struct ComplexityEstimator {}
+
+impl ComplexityEstimator {
+    pub fn measure(program_name: &str, task: &str) -> Result<Vec<Measurement>, RunnerError> {
+        ComplexityEstimator::measure_with(|size| {
+            let input = Generator::generate(task, size, SEED)?;
+            let execution = BinaryRunner::run_measured(program_name, &input)?;
+
+            info!(
+                "{} took {:?} on size {}",
+                program_name, execution.usage.cpu_time, size
+            );
+            Ok(execution.usage.cpu_time)
+        })
+    }
+
+    fn measure_with<F>(mut run: F) -> Result<Vec<Measurement>, RunnerError>
+    where
+        F: FnMut(u32) -> Result<Duration, RunnerError>,
+    {
+        let linear = (LINEAR_STEP..=LINEAR_END).step_by(LINEAR_STEP as usize);
+        let doubling = (0..MAX_POINTS).map(|i| FIRST_SIZE << i);
+        let mut measurements = Vec::new();
+
+        for size in linear.chain(doubling) {
+            // Going over the time limit ends the measurements like going over
+            // the budget, unless it happens right away.
+            let time = match run(size) {
+                Err(RunnerError::Timeout) if !measurements.is_empty() => break,
+                time => time?,
+            };
+
+            measurements.push(Measurement { size, time });
+
+            if time > TIME_BUDGET {
+                break;
+            }
+        }
+
+        Ok(measurements)
+    }
+
+    // Runs on the small sizes are mostly start-up and noise, they are only
+    // fitted when the program does not get far past them, exponential included.
+    pub fn best_fit(measurements: &[Measurement]) -> Option<Fit> {
+        let first_doubled = measurements
+            .iter()
+            .position(|m| m.size >= FIRST_SIZE)
+            .unwrap_or(measurements.len());
+        let doubled = &measurements[first_doubled..];
+
+        if doubled.len() >= MIN_POINTS {
+            return ComplexityEstimator::best_polynomial(doubled);
+        }
+
+        ComplexityEstimator::best_polynomial(measurements)
+            .into_iter()
+            .chain(ComplexityEstimator::fit_exponential(measurements))
+            .min_by(|a, b| a.error.partial_cmp(&b.error).unwrap())
+    }
+
+    fn best_polynomial(measurements: &[Measurement]) -> Option<Fit> {
+        Complexity::POLYNOMIAL
+            .iter()
+            .filter_map(|&complexity| ComplexityEstimator::fit(complexity, measurements))
+            .min_by(|a, b| a.error.partial_cmp(&b.error).unwrap())
+    }
+
+    // Weighted least squares of t = offset + factor * f(n), weighted by 1 / t²
+    // so that every point contributes its relative error.
+    fn fit(complexity: Complexity, measurements: &[Measurement]) -> Option<Fit> {
+        let points: Vec<(f64, f64)> = measurements
+            .iter()
+            .map(|m| {
+                (
+                    complexity.value(m.size as f64),
+                    m.time.as_secs_f64().max(MIN_TIME),
+                )
+            })
+            .collect();
+
+        if points.len() < MIN_POINTS || points.iter().any(|(f, _)| !f.is_finite()) {
+            return None;
+        }
+
+        let (mut sw, mut swf, mut swff, mut swt, mut swft) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for &(f, t) in &points {
+            let w = 1.0 / (t * t);
+            sw += w;
+            swf += w * f;
+            swff += w * f * f;
+            swt += w * t;
+            swft += w * f * t;
+        }
+
+        let determinant = sw * swff - swf * swf;
+        let (offset, factor) = if determinant.abs() < f64::EPSILON * sw * swff {
+            (swt / sw, 0.0)
+        } else {
+            (
+                (swff * swt - swf * swft) / determinant,
+                (sw * swft - swf * swt) / determinant,
+            )
+        };
+
+        if factor < 0.0 || !factor.is_finite() || !offset.is_finite() {
+            return None;
+        }
+
+        let error = points
+            .iter()
+            .map(|&(f, t)| ((offset + factor * f - t) / t).powi(2))
+            .sum::<f64>()
+            / points.len() as f64;
+
+        Some(Fit {
+            complexity,
+            offset,
+            factor,
+            error,
+        })
+    }
+
+    // Least squares of ln t = offset + factor * n, in which every point already
+    // contributes its relative error, over the runs past the start-up.
+    fn fit_exponential(measurements: &[Measurement]) -> Option<Fit> {
+        let times: Vec<f64> = measurements
+            .iter()
+            .map(|m| m.time.as_secs_f64().max(MIN_TIME))
+            .collect();
+        let fastest = times.iter().cloned().fold(f64::INFINITY, f64::min);
+        let points: Vec<(f64, f64)> = measurements
+            .iter()
+            .zip(times)
+            .filter(|&(_, t)| t >= GROWTH_FACTOR * fastest)
+            .map(|(m, t)| (m.size as f64, t))
+            .collect();
+
+        if points.len() < MIN_POINTS {
+            return None;
+        }
+
+        let count = points.len() as f64;
+        let (mut sn, mut snn, mut sl, mut snl) = (0.0, 0.0, 0.0, 0.0);
+        for &(n, t) in &points {
+            sn += n;
+            snn += n * n;
+            sl += t.ln();
+            snl += n * t.ln();
+        }
+
+        let factor = (count * snl - sn * sl) / (count * snn - sn * sn);
+        let offset = (sl - factor * sn) / count;
+
+        if factor <= 0.0 || !factor.is_finite() || !offset.is_finite() {
+            return None;
+        }
+
+        let error = points
+            .iter()
+            .map(|&(n, t)| (((offset + factor * n).exp() - t) / t).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some(Fit {
+            complexity: Complexity::Exponential,
+            offset,
+            factor,
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::binary_runner::{BinaryRunner, ProcessOptions};
+    use crate::runners::compiler::{Compiler, Language};
+    use crate::runners::complexity::{Complexity, ComplexityEstimator, Measurement};
+    use std::process::Command;
+    use std::time::Duration;
+
+    fn measurements(time: impl Fn(f64) -> f64) -> Vec<Measurement> {
+        (0..8)
+            .map(|i| 100 << i)
+            .map(|size| Measurement {
+                size,
+                time: Duration::from_secs_f64(0.001 + time(size as f64)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_fit_exponential() {
+        let source = "#include <stdio.h>
+int main(void) {
+    int n;
+    scanf(\"%d\", &n);
+    for (volatile long i = 0; i < 1L << n; i++) {}
+    return 0;
+}";
+        let binary = Compiler::compile(source, Language::C, Language::C.flags()).unwrap();
+
+        let measurements = ComplexityEstimator::measure_with(|size| {
+            let mut command = Command::new(&binary);
+            let options = ProcessOptions::default();
+            BinaryRunner::execute_command_with(&mut command, &size.to_string(), &options)
+                .map(|process| process.usage.cpu_time)
+        })
+        .unwrap();
+        let fit = ComplexityEstimator::best_fit(&measurements).unwrap();
+
+        assert_eq!(fit.complexity, Complexity::Exponential);
+        assert!(measurements.iter().all(|m| m.size <= 60));
+    }
+
+    #[test]
+    fn should_fit_linear() {
+        let fit = ComplexityEstimator::best_fit(&measurements(|n| n * 1e-6)).unwrap();
+
+        assert_eq!(fit.complexity, Complexity::Linear);
+    }
+
+    #[test]
+    fn should_fit_quadratic() {
+        let fit = ComplexityEstimator::best_fit(&measurements(|n| n * n * 1e-9)).unwrap();
+
+        assert_eq!(fit.complexity, Complexity::Quadratic);
+    }
+
+    #[test]
+    fn should_fit_linearithmic() {
+        let fit = ComplexityEstimator::best_fit(&measurements(|n| n * n.log2() * 1e-7)).unwrap();
+
+        assert_eq!(fit.complexity, Complexity::Linearithmic);
+    }
+
+    #[test]
+    fn should_not_fit_too_few_points() {
+        let mut measurements = measurements(|n| n * 1e-6);
+        measurements.truncate(2);
+
+        assert_eq!(ComplexityEstimator::best_fit(&measurements), None);
+    }
+}
@@ -0,0 +1,20 @@
+use sha2::{Digest, Sha256};
+
+// Unlike `DefaultHasher`, SHA-256 stays the same across Rust releases,
+// so the digest can be stored and compared later.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::digest::sha256_hex;
+
+    #[test]
+    fn should_hash_with_sha256() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::runner_error::RunnerError;
+use tracing::info;
+
+pub struct Generator {}
+
+impl Generator {
+    pub fn name(task: &str) -> String {
+        format!("{}.gen", task)
+    }
+
+    pub fn generate(task: &str, size: u32, seed: u64) -> Result<String, RunnerError> {
+        info!(
+            "Generating input for {} (size {}, seed {})",
+            task, size, seed
+        );
+
+        BinaryRunner::execute(
+            &Generator::name(task),
+            &[seed.to_string(), size.to_string()],
+            "",
+        )
+    }
+}
@@ -0,0 +1,272 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::process_group::ProcessGroup;
+use crate::runners::resource_control::RESOURCE_CONTROL;
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::{Environment, ScratchDirectory};
+use crate::runners::syscall_filter::{SyscallFilter, SyscallPolicy};
+use crate::runners::validator::Validator;
+use crate::tasks::task_config::TaskConfig;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use wait_timeout::ChildExt;
+
+type InteractionResult = Result<Interaction, RunnerError>;
+type Transcript = Arc<Mutex<Vec<Exchange>>>;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub enum Exchange {
+    Program(String),
+    Interactor(String),
+}
+
+impl fmt::Display for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exchange::Program(line) => write!(f, "> {}", line),
+            Exchange::Interactor(line) => write!(f, "< {}", line),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub enum InteractorVerdict {
+    Accepted(String),
+    Rejected(String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Interaction {
+    pub verdict: InteractorVerdict,
+    pub transcript: Vec<Exchange>,
+}
+
+pub struct InteractiveRunner {}
+
+impl InteractiveRunner {
+    pub fn run(program_name: &str, interactor_name: &str, input: &str) -> InteractionResult {
+        if input.is_empty() {
+            return Err(RunnerError::NoInput);
+        }
+
+        Validator::validate(program_name, input)?;
+
+        let program_path = InteractiveRunner::find_binary(program_name)?;
+        let interactor_path = InteractiveRunner::find_binary(interactor_name)?;
+        let scratch = ScratchDirectory::create()?;
+        let input_path = scratch.write("interaction.txt", input)?;
+        let config = TaskConfig::load(program_name);
+        let mut program_command = Command::new(program_path);
+        let program_cgroup = RESOURCE_CONTROL.confine(
+            &mut program_command,
+            &config.resource_limits(),
+            config.time_limit(),
+        )?;
+
+        let mut interactor = InteractiveRunner::spawn(
+            scratch
+                .prepare(&mut Command::new(interactor_path), &Environment::new())
+                .arg(input_path)
+                .stderr(Stdio::piped()),
+            config.reference_syscalls(),
+        )?;
+        let interactor_group = ProcessGroup::track(&interactor);
+        let mut program = match InteractiveRunner::spawn(
+            scratch
+                .prepare(&mut program_command, &config.environment)
+                .stderr(Stdio::null()),
+            config.submission_syscalls(),
+        ) {
+            Ok(program) => program,
+            Err(e) => {
+                let _ = interactor.kill();
+                return Err(e);
+            }
+        };
+        let program_group = ProcessGroup::track(&program);
+
+        info!(
+            "Program {} started with interactor {}",
+            program_name, interactor_name
+        );
+
+        let transcript = Arc::new(Mutex::new(Vec::new()));
+        let relays = vec![
+            InteractiveRunner::relay(
+                program.stdout.take(),
+                interactor.stdin.take(),
+                transcript.clone(),
+                Exchange::Program,
+            ),
+            InteractiveRunner::relay(
+                interactor.stdout.take(),
+                program.stdin.take(),
+                transcript.clone(),
+                Exchange::Interactor,
+            ),
+        ];
+
+        let outcome =
+            InteractiveRunner::wait_both(&mut program, &mut interactor, config.time_limit());
+        drop(program_group);
+        drop(interactor_group);
+
+        for relay in relays {
+            if relay.join().is_err() {
+                warn!("Interaction relay panicked");
+            }
+        }
+
+        let (program_status, interactor_status) = outcome?;
+        if program_status.signal() == Some(libc::SIGSYS) {
+            return Err(RunnerError::ForbiddenSyscall);
+        }
+        if program_cgroup
+            .map(|cgroup| cgroup.oom_killed())
+            .unwrap_or(false)
+        {
+            return Err(RunnerError::MemoryLimitExceeded);
+        }
+
+        info!(
+            "{} returned {}, {} returned {}",
+            program_name, program_status, interactor_name, interactor_status
+        );
+
+        if !program_status.success() && interactor_status.success() {
+            error!("{} crashed!", program_name);
+            return Err(RunnerError::Crash);
+        }
+
+        let comment = InteractiveRunner::read_comment(&mut interactor);
+        let verdict = if interactor_status.success() {
+            InteractorVerdict::Accepted(comment)
+        } else {
+            InteractorVerdict::Rejected(comment)
+        };
+
+        let transcript = match Arc::try_unwrap(transcript) {
+            Ok(transcript) => transcript.into_inner().unwrap(),
+            Err(_) => return Err(RunnerError::Other("transcript is still in use".to_string())),
+        };
+
+        Ok(Interaction {
+            verdict,
+            transcript,
+        })
+    }
+
+    fn find_binary(program_name: &str) -> Result<String, RunnerError> {
+        match BinaryRunner::binary_path(program_name) {
+            Ok(path) => Ok(path.to_string_lossy().to_string()),
+            Err(_) => Err(RunnerError::NotFound),
+        }
+    }
+
+    // The filter goes last, after the hooks of the cgroup and the group.
+    fn spawn(command: &mut Command, syscalls: SyscallPolicy) -> Result<Child, RunnerError> {
+        SyscallFilter::apply(ProcessGroup::isolate(command), syscalls)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| RunnerError::Other(e.to_string()))
+    }
+
+    fn relay<F>(
+        from: Option<ChildStdout>,
+        to: Option<ChildStdin>,
+        transcript: Transcript,
+        exchange: F,
+    ) -> JoinHandle<()>
+    where
+        F: Fn(String) -> Exchange + Send + 'static,
+    {
+        thread::spawn(move || {
+            let (from, mut to) = match (from, to) {
+                (Some(from), Some(to)) => (from, to),
+                _ => return,
+            };
+
+            let mut reader = BufReader::new(from);
+            let mut line = String::new();
+
+            while let Ok(read) = reader.read_line(&mut line) {
+                if read == 0 {
+                    break;
+                }
+
+                transcript
+                    .lock()
+                    .unwrap()
+                    .push(exchange(line.trim_end().to_string()));
+
+                if to
+                    .write_all(line.as_bytes())
+                    .and_then(|_| to.flush())
+                    .is_err()
+                {
+                    break;
+                }
+
+                line.clear();
+            }
+        })
+    }
+
+    fn wait_both(
+        program: &mut Child,
+        interactor: &mut Child,
+        time_limit: Duration,
+    ) -> Result<(ExitStatus, ExitStatus), RunnerError> {
+        let deadline = Instant::now() + time_limit;
+
+        let program_status = InteractiveRunner::wait_until(program, deadline);
+        let interactor_status = InteractiveRunner::wait_until(interactor, deadline);
+
+        match (program_status, interactor_status) {
+            (Some(program), Some(interactor)) => Ok((program, interactor)),
+            _ => {
+                let _ = program.kill();
+                let _ = interactor.kill();
+                Err(RunnerError::Timeout)
+            }
+        }
+    }
+
+    fn wait_until(child: &mut Child, deadline: Instant) -> Option<ExitStatus> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        child.wait_timeout(remaining).ok().flatten()
+    }
+
+    fn read_comment(interactor: &mut Child) -> String {
+        let mut comment = String::new();
+
+        if let Some(stderr) = interactor.stderr.as_mut() {
+            let _ = stderr.read_to_string(&mut comment);
+        }
+
+        comment.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::interactive_runner::InteractiveRunner;
+    use crate::runners::runner_error::RunnerError;
+
+    #[test]
+    fn should_return_error_on_empty_input() {
+        let out = InteractiveRunner::run("not found", "not found", "");
+
+        assert!(out.is_err());
+        assert_eq!(out.unwrap_err(), RunnerError::NoInput);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::runners::benchmark::{Benchmark, BenchmarkResult};
+use crate::runners::binary_runner::Execution;
+use crate::runners::compiler::{Compiler, Language};
+use crate::runners::complexity::{ComplexityEstimator, Measurement};
+use crate::runners::generator::Generator;
+use crate::runners::interactive_runner::{Interaction, InteractiveRunner};
+use crate::runners::run_request::RunRequest;
+use crate::runners::runner_error::RunnerError;
+use crate::runners::sanitizer::{Sanitizer, SanitizerMode};
+use crate::tasks::calibration::{Calibration, Calibrator};
+use crate::tasks::grader::{Grade, Grader};
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::test_suite::TestSuite;
+use crate::tasks::verdict::Verdict;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub type JobResult = Result<JobOutput, RunnerError>;
+
+// Longest time a worker may take to answer. Jobs running a program many
+// times get more, single runs are kept well above `MAX_TIME_LIMIT`.
+const JOB_TIMEOUT: Duration = Duration::from_secs(90);
+const LONG_JOB_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+// Everything that runs code of users, so that it can be sent to a worker
+// instead of running on the bot's host.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Job {
+    Run(RunRequest),
+    Generate {
+        task: String,
+        size: u32,
+        seed: u64,
+    },
+    Interact {
+        program: String,
+        interactor: String,
+        input: String,
+    },
+    Benchmark {
+        program: String,
+        input: String,
+        runs: usize,
+    },
+    Complexity {
+        program: String,
+        task: String,
+    },
+    Sanitize {
+        source: String,
+        language: Language,
+        input: String,
+        mode: SanitizerMode,
+    },
+    Judge {
+        submission: String,
+        task: String,
+        source: String,
+        language: Language,
+        time_limit_ms: Option<u64>,
+    },
+    Calibrate {
+        task: String,
+        multiplier: f64,
+        floor: Duration,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum JobOutput {
+    Executed(Execution),
+    Generated(String),
+    Interacted(Interaction),
+    Benchmarked(BenchmarkResult),
+    Measured(Vec<Measurement>),
+    Sanitized(String),
+    Judged(Grade),
+    Calibrated(Calibration),
+}
+
+impl Job {
+    pub fn execute(&self) -> JobResult {
+        match self {
+            Job::Run(request) => request.run().map(JobOutput::Executed),
+            Job::Generate { task, size, seed } => {
+                Generator::generate(task, *size, *seed).map(JobOutput::Generated)
+            }
+            Job::Interact {
+                program,
+                interactor,
+                input,
+            } => InteractiveRunner::run(program, interactor, input).map(JobOutput::Interacted),
+            Job::Benchmark {
+                program,
+                input,
+                runs,
+            } => Benchmark::run(program, input, *runs).map(JobOutput::Benchmarked),
+            Job::Complexity { program, task } => {
+                ComplexityEstimator::measure(program, task).map(JobOutput::Measured)
+            }
+            Job::Sanitize {
+                source,
+                language,
+                input,
+                mode,
+            } => Sanitizer::run(source, *language, input, *mode).map(JobOutput::Sanitized),
+            Job::Judge {
+                submission,
+                task,
+                source,
+                language,
+                time_limit_ms,
+            } => Job::judge(submission, task, source, *language, *time_limit_ms)
+                .map(JobOutput::Judged),
+            Job::Calibrate {
+                task,
+                multiplier,
+                floor,
+            } => Calibrator::calibrate(task, *multiplier, *floor).map(JobOutput::Calibrated),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        match self {
+            Job::Benchmark { .. }
+            | Job::Complexity { .. }
+            | Job::Judge { .. }
+            | Job::Calibrate { .. } => LONG_JOB_TIMEOUT,
+            _ => JOB_TIMEOUT,
+        }
+    }
+
+    // Only outputs of failed tests are kept, they are all that is shown.
+    // Compilation errors are returned as errors to show the compiler's output.
+    // `time_limit_ms` comes from the bot, the worker's copy may be older.
+    fn judge(
+        submission: &str,
+        task: &str,
+        source: &str,
+        language: Language,
+        time_limit_ms: Option<u64>,
+    ) -> Result<Grade, RunnerError> {
+        let mut config = TaskConfig::load(task);
+        if time_limit_ms.is_some() {
+            config.time_limit_ms = time_limit_ms;
+        }
+        let suite = TestSuite::load(task, &config)?;
+
+        let executable = Compiler::compile(source, language, language.flags())?;
+        let mut grade = Grader::grade(submission, &executable, &suite, &config);
+
+        for test in &mut grade.tests {
+            if test.verdict == Verdict::Accepted {
+                test.output.clear();
+            }
+        }
+
+        Ok(grade)
+    }
+}
+
+fn unexpected(output: JobOutput) -> RunnerError {
+    RunnerError::Other(format!("unexpected job output {:?}", output))
+}
+
+impl JobOutput {
+    pub fn execution(self) -> Result<Execution, RunnerError> {
+        match self {
+            JobOutput::Executed(execution) => Ok(execution),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn generated(self) -> Result<String, RunnerError> {
+        match self {
+            JobOutput::Generated(input) => Ok(input),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn interaction(self) -> Result<Interaction, RunnerError> {
+        match self {
+            JobOutput::Interacted(interaction) => Ok(interaction),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn benchmark(self) -> Result<BenchmarkResult, RunnerError> {
+        match self {
+            JobOutput::Benchmarked(result) => Ok(result),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn measurements(self) -> Result<Vec<Measurement>, RunnerError> {
+        match self {
+            JobOutput::Measured(measurements) => Ok(measurements),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn sanitized(self) -> Result<String, RunnerError> {
+        match self {
+            JobOutput::Sanitized(output) => Ok(output),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn grade(self) -> Result<Grade, RunnerError> {
+        match self {
+            JobOutput::Judged(grade) => Ok(grade),
+            output => Err(unexpected(output)),
+        }
+    }
+
+    pub fn calibration(self) -> Result<Calibration, RunnerError> {
+        match self {
+            JobOutput::Calibrated(calibration) => Ok(calibration),
+            output => Err(unexpected(output)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::job::{Job, JobOutput};
+    use crate::runners::run_request::RunRequest;
+    use crate::runners::runner_error::RunnerError;
+
+    #[test]
+    fn should_run_job_locally() {
+        let job = Job::Run(RunRequest::new("not found", "1 2"));
+
+        assert_eq!(job.execute().unwrap_err(), RunnerError::NotFound);
+    }
+
+    #[test]
+    fn should_reject_unexpected_output() {
+        let output = JobOutput::Generated("1 2".to_string());
+
+        assert!(output.execution().is_err());
+    }
+}
@@ -0,0 +1,17 @@
+pub mod benchmark;
+pub mod binary_runner;
+pub mod compiler;
+pub mod complexity;
+pub mod digest;
+pub mod generator;
+pub mod interactive_runner;
+pub mod job;
+pub mod process_group;
+pub mod resource_control;
+pub mod resource_usage;
+pub mod run_request;
+pub mod runner_error;
+pub mod sanitizer;
+pub mod scratch_directory;
+pub mod syscall_filter;
+pub mod validator;
@@ -0,0 +1,152 @@
+use std::fs;
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, error};
+
+const CLEANUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Groups of runs in progress, so that shutdown can kill all of them.
+static ACTIVE_GROUPS: Mutex<Vec<libc::pid_t>> = Mutex::new(Vec::new());
+
+// Every run gets its own process group, which also contains everything the
+// program forks. Dropping the group kills whatever is still running in it.
+pub struct ProcessGroup {
+    id: libc::pid_t,
+}
+
+impl ProcessGroup {
+    pub fn isolate(command: &mut Command) -> &mut Command {
+        // setpgid is async-signal-safe, so it may run between fork and exec.
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setpgid(0, 0) == 0 {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            })
+        }
+    }
+
+    pub fn track(child: &Child) -> ProcessGroup {
+        let id = child.id() as libc::pid_t;
+        ACTIVE_GROUPS.lock().unwrap().push(id);
+
+        ProcessGroup { id }
+    }
+
+    pub fn kill(&self) {
+        unsafe {
+            libc::kill(-self.id, libc::SIGKILL);
+        }
+    }
+
+    // Processes of the group that have not exited yet, zombies excluded.
+    pub fn remaining(&self) -> Vec<libc::pid_t> {
+        let entries = match fs::read_dir("/proc") {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()?.parse::<libc::pid_t>().ok())
+            .filter(|pid| {
+                let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap_or_default();
+                ProcessGroup::is_running_member(&stat, self.id)
+            })
+            .collect()
+    }
+
+    pub fn kill_all() {
+        for id in ACTIVE_GROUPS.lock().unwrap().iter() {
+            unsafe {
+                libc::kill(-id, libc::SIGKILL);
+            }
+        }
+    }
+
+    // `pid (comm) state ppid pgrp ...`, comm may contain spaces and parentheses.
+    fn is_running_member(stat: &str, group: libc::pid_t) -> bool {
+        let fields: Vec<&str> = match stat.rfind(')') {
+            Some(end) => stat[end + 1..].split_whitespace().collect(),
+            None => return false,
+        };
+
+        match (fields.first(), fields.get(2)) {
+            (Some(&state), Some(pgrp)) => state != "Z" && pgrp.parse() == Ok(group),
+            _ => false,
+        }
+    }
+}
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        self.kill();
+
+        let start = Instant::now();
+        let mut remaining = self.remaining();
+
+        while !remaining.is_empty() && start.elapsed() < CLEANUP_TIMEOUT {
+            thread::sleep(Duration::from_millis(10));
+            remaining = self.remaining();
+        }
+
+        if remaining.is_empty() {
+            debug!("Process group {} is empty", self.id);
+        } else {
+            error!(
+                "Processes {:?} of group {} survived SIGKILL",
+                remaining, self.id
+            );
+        }
+
+        ACTIVE_GROUPS.lock().unwrap().retain(|&id| id != self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::process_group::ProcessGroup;
+    use std::process::{Command, Stdio};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn should_kill_forked_processes() {
+        let mut child = ProcessGroup::isolate(&mut Command::new("sh"))
+            .arg("-c")
+            .arg("sleep 30 & sleep 30 & wait")
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+        let group = ProcessGroup::track(&child);
+
+        assert!(!group.remaining().is_empty());
+
+        group.kill();
+        let _ = child.wait();
+        let emptied = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            group.remaining().is_empty()
+        });
+
+        assert!(emptied);
+    }
+
+    #[test]
+    fn should_parse_process_group() {
+        let stat = "42 (a (weird) name) S 1 40 40 0 -1";
+
+        assert!(ProcessGroup::is_running_member(stat, 40));
+        assert!(!ProcessGroup::is_running_member(stat, 42));
+        assert!(!ProcessGroup::is_running_member(
+            "43 (sleep) Z 1 40 40 0 -1",
+            40
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::runners::runner_error::RunnerError;
+use once_cell::sync::Lazy;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub const DEFAULT_PIDS_LIMIT: u64 = 64;
+
+const CGROUP_VARIABLE: &str = "JANOSIK_CGROUP";
+const LEAF_CGROUP: &str = "bot";
+const CONTROLLERS: &[&str] = &["memory", "pids", "cpu"];
+const CPU_PERIOD_US: u64 = 100_000;
+
+pub static RESOURCE_CONTROL: Lazy<ResourceControl> = Lazy::new(ResourceControl::detect);
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResourceLimits {
+    pub memory_bytes: Option<u64>,
+    pub pids: Option<u64>,
+    pub cpu_cores: Option<f64>,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        ResourceLimits {
+            memory_bytes: None,
+            pids: Some(DEFAULT_PIDS_LIMIT),
+            cpu_cores: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResourceControl {
+    // A delegated cgroup v2 subtree, every run gets a child cgroup in it.
+    Cgroup(PathBuf),
+    // Limits of the program itself: RLIMIT_AS, RLIMIT_NPROC, which is per user,
+    // and RLIMIT_CPU instead of the CPU cores.
+    Rlimits,
+}
+
+impl fmt::Display for ResourceControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceControl::Cgroup(root) => write!(f, "cgroup v2 under {:?}", root),
+            ResourceControl::Rlimits => write!(f, "rlimits"),
+        }
+    }
+}
+
+impl ResourceControl {
+    pub fn log_mode() {
+        info!("Resource control: {}", *RESOURCE_CONTROL);
+
+        if *RESOURCE_CONTROL == ResourceControl::Rlimits {
+            warn!(
+                "Without a cgroup the memory limit covers only the address space, \
+                 the pids limit is shared with all processes of the user \
+                 and CPU cores are not limited, only CPU time"
+            );
+        }
+    }
+
+    fn detect() -> ResourceControl {
+        let root = match env::var(CGROUP_VARIABLE) {
+            Ok(root) => PathBuf::from(root),
+            Err(_) => return ResourceControl::Rlimits,
+        };
+
+        let usable =
+            ResourceControl::enable_controllers(&root).and_then(|_| ResourceControl::probe(&root));
+
+        match usable {
+            Ok(()) => ResourceControl::Cgroup(root),
+            Err(e) => {
+                warn!(
+                    "Cannot use cgroup {:?}, falling back to rlimits: {}",
+                    root, e
+                );
+                ResourceControl::Rlimits
+            }
+        }
+    }
+
+    // A cgroup with controllers enabled for its children cannot hold processes
+    // itself, so the ones already in the root, like the bot, move to a leaf.
+    fn enable_controllers(root: &Path) -> io::Result<()> {
+        let available = fs::read_to_string(root.join("cgroup.controllers"))?;
+
+        for controller in CONTROLLERS {
+            if !available.split_whitespace().any(|c| c == *controller) {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} controller is not delegated", controller),
+                ));
+            }
+        }
+
+        let leaf = root.join(LEAF_CGROUP);
+        match fs::create_dir(&leaf) {
+            Err(e) if e.kind() != io::ErrorKind::AlreadyExists => return Err(e),
+            _ => {}
+        }
+
+        for pid in fs::read_to_string(root.join("cgroup.procs"))?.lines() {
+            fs::write(leaf.join("cgroup.procs"), pid)?;
+        }
+
+        let enabled: Vec<String> = CONTROLLERS.iter().map(|c| format!("+{}", c)).collect();
+        fs::write(root.join("cgroup.subtree_control"), enabled.join(" "))
+    }
+
+    // Runs a program in a run cgroup, writable files do not yet mean that
+    // processes can be moved into it.
+    fn probe(root: &Path) -> io::Result<()> {
+        let cgroup = RunCgroup::create(root, &ResourceLimits::default())?;
+        let mut command = Command::new("true");
+        cgroup.attach(&mut command);
+
+        let status = command.status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("probe exited with {}", status)));
+        }
+
+        Ok(())
+    }
+
+    // Applies the limits to the command, the returned cgroup has to live until
+    // the program exits.
+    pub fn confine(
+        &self,
+        command: &mut Command,
+        limits: &ResourceLimits,
+        time_limit: Duration,
+    ) -> Result<Option<RunCgroup>, RunnerError> {
+        match self {
+            ResourceControl::Cgroup(root) => {
+                let cgroup = RunCgroup::create(root, limits)
+                    .map_err(|e| RunnerError::Other(format!("cannot create cgroup: {}", e)))?;
+                cgroup.attach(command);
+                Ok(Some(cgroup))
+            }
+            ResourceControl::Rlimits => {
+                if let Some(memory) = limits.memory_bytes {
+                    ResourceControl::limit(command, libc::RLIMIT_AS, memory, memory);
+                }
+
+                // Every task of the user counts, so the program may start only
+                // `pids` more. Not enforced for root.
+                if let Some(pids) = limits.pids {
+                    let processes = ResourceControl::user_tasks() + pids;
+                    ResourceControl::limit(command, libc::RLIMIT_NPROC, processes, processes);
+                }
+
+                // SIGXCPU a second after the time limit, SIGKILL a second later,
+                // for what runs on after the program is killed.
+                let seconds = time_limit.as_secs() + 2;
+                ResourceControl::limit(command, libc::RLIMIT_CPU, seconds, seconds + 1);
+
+                Ok(None)
+            }
+        }
+    }
+
+    fn limit(command: &mut Command, resource: libc::__rlimit_resource_t, soft: u64, hard: u64) {
+        let limit = libc::rlimit {
+            rlim_cur: soft as libc::rlim_t,
+            rlim_max: hard as libc::rlim_t,
+        };
+
+        // setrlimit is async-signal-safe, so it may run between fork and exec.
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setrlimit(resource, &limit) == 0 {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            });
+        }
+    }
+
+    // Processes and threads of the user, which RLIMIT_NPROC counts together.
+    fn user_tasks() -> u64 {
+        let uid = unsafe { libc::getuid() }.to_string();
+        let entries = match fs::read_dir("/proc") {
+            Ok(entries) => entries,
+            Err(_) => return 0,
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().parse::<u32>().is_ok())
+            .filter_map(|entry| fs::read_to_string(entry.path().join("status")).ok())
+            .filter(|status| ResourceControl::status_value(status, "Uid:") == Some(uid.as_str()))
+            .filter_map(|status| {
+                ResourceControl::status_value(&status, "Threads:")?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .sum()
+    }
+
+    // The first value of a line of /proc/<pid>/status, the real one for ids.
+    fn status_value<'a>(status: &'a str, key: &str) -> Option<&'a str> {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(key))?
+            .split_whitespace()
+            .next()
+    }
+}
+
+pub struct RunCgroup {
+    path: PathBuf,
+    procs: File,
+}
+
+impl RunCgroup {
+    fn create(root: &Path, limits: &ResourceLimits) -> io::Result<RunCgroup> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = root.join(format!("run-{}-{}", process::id(), id));
+
+        fs::create_dir(&path)?;
+
+        if let Some(memory) = limits.memory_bytes {
+            fs::write(path.join("memory.max"), memory.to_string())?;
+            // Without swap the memory limit cannot be dodged.
+            let _ = fs::write(path.join("memory.swap.max"), "0");
+        }
+
+        if let Some(pids) = limits.pids {
+            fs::write(path.join("pids.max"), pids.to_string())?;
+        }
+
+        if let Some(cores) = limits.cpu_cores {
+            let quota = (cores * CPU_PERIOD_US as f64) as u64;
+            fs::write(path.join("cpu.max"), format!("{} {}", quota, CPU_PERIOD_US))?;
+        }
+
+        let procs = OpenOptions::new()
+            .write(true)
+            .open(path.join("cgroup.procs"))?;
+
+        Ok(RunCgroup { path, procs })
+    }
+
+    fn attach(&self, command: &mut Command) {
+        let procs = self.procs.as_raw_fd();
+
+        // Writing "0" moves the writing process, that is the child before exec.
+        unsafe {
+            command.pre_exec(move || {
+                if libc::write(procs, b"0".as_ptr() as *const libc::c_void, 1) == 1 {
+                    Ok(())
+                } else {
+                    Err(io::Error::last_os_error())
+                }
+            });
+        }
+    }
+
+    pub fn peak_memory_kb(&self) -> Option<u64> {
+        let peak: u64 = self.read("memory.peak")?.trim().parse().ok()?;
+        Some(peak / 1024)
+    }
+
+    pub fn cpu_time(&self) -> Option<Duration> {
+        let usage = RunCgroup::stat_value(&self.read("cpu.stat")?, "usage_usec")?;
+        Some(Duration::from_micros(usage))
+    }
+
+    pub fn oom_killed(&self) -> bool {
+        self.read("memory.events")
+            .and_then(|events| RunCgroup::stat_value(&events, "oom_kill"))
+            .map(|kills| kills > 0)
+            .unwrap_or(false)
+    }
+
+    fn read(&self, file: &str) -> Option<String> {
+        fs::read_to_string(self.path.join(file)).ok()
+    }
+
+    fn stat_value(stat: &str, key: &str) -> Option<u64> {
+        stat.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<&str>>())
+            .find(|fields| fields.first() == Some(&key))
+            .and_then(|fields| fields.get(1)?.parse().ok())
+    }
+}
+
+impl Drop for RunCgroup {
+    fn drop(&mut self) {
+        let _ = fs::write(self.path.join("cgroup.kill"), "1");
+
+        // The cgroup can be removed only once the killed processes are gone.
+        for _ in 0..100 {
+            if fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        warn!("Cannot remove cgroup {:?}", self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::resource_control::{
+        ResourceControl, ResourceLimits, RunCgroup, DEFAULT_PIDS_LIMIT,
+    };
+    use std::process::Command;
+    use std::time::Duration;
+
+    #[test]
+    fn should_not_limit_memory_by_default() {
+        let limits = ResourceLimits::default();
+
+        assert_eq!(limits.memory_bytes, None);
+        assert_eq!(limits.pids, Some(DEFAULT_PIDS_LIMIT));
+    }
+
+    #[test]
+    fn should_limit_processes_and_cpu_time_with_rlimits() {
+        let mut command = Command::new("cat");
+        command.arg("/proc/self/limits");
+
+        ResourceControl::Rlimits
+            .confine(
+                &mut command,
+                &ResourceLimits::default(),
+                Duration::from_secs(5),
+            )
+            .unwrap();
+        let output = String::from_utf8(command.output().unwrap().stdout).unwrap();
+        let limit = |name: &str| -> Vec<String> {
+            let line = output.lines().find(|l| l.starts_with(name)).unwrap();
+            line[name.len()..]
+                .split_whitespace()
+                .map(str::to_string)
+                .collect()
+        };
+
+        assert_eq!(limit("Max cpu time")[..2], ["7", "8"]);
+        assert!(limit("Max processes")[0].parse::<u64>().unwrap() > DEFAULT_PIDS_LIMIT);
+    }
+
+    #[test]
+    fn should_read_status_values() {
+        let status = "Name:\tsleep\nUid:\t1000\t1000\t1000\t1000\nThreads:\t3\n";
+
+        assert_eq!(ResourceControl::status_value(status, "Uid:"), Some("1000"));
+        assert_eq!(ResourceControl::status_value(status, "Threads:"), Some("3"));
+        assert_eq!(ResourceControl::status_value(status, "Gid:"), None);
+    }
+
+    #[test]
+    fn should_read_stat_values() {
+        let stat = "usage_usec 1523\nuser_usec 1000\nsystem_usec 523\n";
+
+        assert_eq!(RunCgroup::stat_value(stat, "usage_usec"), Some(1523));
+        assert_eq!(RunCgroup::stat_value(stat, "user_usec"), Some(1000));
+        assert_eq!(RunCgroup::stat_value(stat, "nr_periods"), None);
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ResourceUsage {
+    pub wall_time: Duration,
+    pub cpu_time: Duration,
+    pub max_rss_kb: u64,
+}
+
+impl ResourceUsage {
+    pub fn from_rusage(rusage: &libc::rusage, wall_time: Duration) -> Self {
+        ResourceUsage {
+            wall_time,
+            cpu_time: to_duration(rusage.ru_utime) + to_duration(rusage.ru_stime),
+            max_rss_kb: rusage.ru_maxrss as u64,
+        }
+    }
+}
+
+fn to_duration(time: libc::timeval) -> Duration {
+    Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+}
@@ -0,0 +1,47 @@
+use crate::runners::binary_runner::{BinaryRunner, Execution};
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::NamedFile;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub type RunResult = Result<Execution, RunnerError>;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RunRequest {
+    pub program: String,
+    pub input: String,
+    pub files: Vec<NamedFile>,
+    // Calibrated limit of the task on the bot's host, workers may have an older one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit_ms: Option<u64>,
+}
+
+impl RunRequest {
+    pub fn new(program: &str, input: &str) -> RunRequest {
+        RunRequest {
+            program: program.to_string(),
+            input: input.to_string(),
+            files: Vec::new(),
+            time_limit_ms: None,
+        }
+    }
+
+    pub fn with_files(mut self, files: Vec<NamedFile>) -> RunRequest {
+        self.files = files;
+        self
+    }
+
+    pub fn with_time_limit(mut self, time_limit_ms: Option<u64>) -> RunRequest {
+        self.time_limit_ms = time_limit_ms;
+        self
+    }
+
+    pub fn run(&self) -> RunResult {
+        BinaryRunner::run_with_files(
+            &self.program,
+            &self.input,
+            &self.files,
+            self.time_limit_ms.map(Duration::from_millis),
+        )
+    }
+}
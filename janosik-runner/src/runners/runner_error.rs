@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub enum RunnerError {
+    NoInput,
+    Timeout,
+    MemoryLimitExceeded,
+    ForbiddenSyscall,
+    NotFound,
+    NoOutput,
+    Crash,
+    InvalidInput(String),
+    CompilationError(String),
+    MemoryError(String),
+    InvalidConfig(String),
+    Other(String),
+}
+
+impl RunnerError {
+    pub fn verdict(&self) -> &'static str {
+        match self {
+            RunnerError::NoInput => "NO_INPUT",
+            RunnerError::Timeout => "TIMEOUT",
+            RunnerError::MemoryLimitExceeded => "MEMORY_LIMIT",
+            RunnerError::ForbiddenSyscall => "FORBIDDEN_SYSCALL",
+            RunnerError::NotFound => "NOT_FOUND",
+            RunnerError::NoOutput => "NO_OUTPUT",
+            RunnerError::Crash => "CRASH",
+            RunnerError::InvalidInput(_) => "INVALID_INPUT",
+            RunnerError::CompilationError(_) => "COMPILATION_ERROR",
+            RunnerError::MemoryError(_) => "MEMORY_ERROR",
+            RunnerError::InvalidConfig(_) => "INVALID_CONFIG",
+            RunnerError::Other(_) => "ERROR",
+        }
+    }
+}
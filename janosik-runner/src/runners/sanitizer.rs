@@ -0,0 +1,316 @@
+use crate::runners::binary_runner::{BinaryRunner, ProcessOptions};
+use crate::runners::compiler::{Compiler, Language};
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::{Environment, SCRATCH_PREFIX};
+use crate::runners::syscall_filter::SyscallPolicy;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use tracing::{error, info};
+
+const MAX_DIAGNOSTIC_LINES: usize = 12;
+const MAX_STACK_FRAMES: usize = 5;
+const VALGRIND_ERROR_CODE: &str = "99";
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum SanitizerMode {
+    Sanitizers,
+    Valgrind,
+}
+
+impl SanitizerMode {
+    fn flags(self) -> &'static [&'static str] {
+        match self {
+            SanitizerMode::Sanitizers => &[
+                "-g",
+                "-O1",
+                "-fno-omit-frame-pointer",
+                "-fsanitize=address,undefined",
+                "-fno-sanitize-recover=undefined",
+            ],
+            SanitizerMode::Valgrind => &["-g", "-O0"],
+        }
+    }
+}
+
+pub struct Sanitizer {}
+
+impl Sanitizer {
+    pub fn is_valgrind_available() -> bool {
+        Command::new("valgrind").arg("--version").output().is_ok()
+    }
+
+    pub fn run(
+        source: &str,
+        language: Language,
+        input: &str,
+        mode: SanitizerMode,
+    ) -> Result<String, RunnerError> {
+        if mode == SanitizerMode::Valgrind && !Sanitizer::is_valgrind_available() {
+            return Err(RunnerError::NotFound);
+        }
+
+        let binary = Compiler::compile(source, language, mode.flags())?;
+
+        let mut environment = Environment::new();
+
+        let mut command = match mode {
+            SanitizerMode::Sanitizers => {
+                // LeakSanitizer stops the program's threads with ptrace, which
+                // the filter forbids. Valgrind still finds leaks.
+                environment.insert("ASAN_OPTIONS".to_string(), "detect_leaks=0".to_string());
+                environment.insert(
+                    "UBSAN_OPTIONS".to_string(),
+                    "print_stacktrace=1:halt_on_error=1".to_string(),
+                );
+                Command::new(&binary)
+            }
+            SanitizerMode::Valgrind => {
+                let mut command = Command::new("valgrind");
+                command
+                    .arg("-q")
+                    .arg(format!("--error-exitcode={}", VALGRIND_ERROR_CODE))
+                    .arg("--leak-check=full")
+                    .arg(&binary);
+                command
+            }
+        };
+
+        let options = ProcessOptions {
+            environment,
+            syscalls: SyscallPolicy::Strict,
+            ..ProcessOptions::default()
+        };
+
+        info!("Running {:?} in {:?} mode", binary, mode);
+        let process = BinaryRunner::execute_command_with(&mut command, input, &options)?;
+
+        let diagnostic = match mode {
+            SanitizerMode::Sanitizers => Sanitizer::sanitizer_diagnostic(&process.stderr),
+            SanitizerMode::Valgrind => Sanitizer::valgrind_diagnostic(&process.stderr),
+        };
+
+        if let Some(diagnostic) = diagnostic {
+            error!("Memory error found");
+            return Err(RunnerError::MemoryError(strip_paths(&diagnostic, &binary)));
+        }
+
+        if process.status_code != 0 {
+            return Err(RunnerError::Crash);
+        }
+
+        if process.stdout.is_empty() {
+            return Err(RunnerError::NoOutput);
+        }
+
+        Ok(process.stdout)
+    }
+
+    fn sanitizer_diagnostic(stderr: &str) -> Option<String> {
+        let lines: Vec<&str> = stderr.lines().collect();
+
+        if let Some(line) = lines.iter().find(|l| is_undefined_behavior_report(l)) {
+            return Some(line.trim().to_string());
+        }
+
+        let start = lines.iter().position(|l| is_sanitizer_report(l))?;
+        let mut diagnostic = vec![strip_pid(lines[start])];
+        let mut frames = 0;
+
+        for line in &lines[start + 1..] {
+            let line = line.trim();
+
+            if line.starts_with('#') {
+                frames += 1;
+                if frames <= MAX_STACK_FRAMES {
+                    diagnostic.push(line.to_string());
+                }
+            } else if line.starts_with("SUMMARY:") {
+                diagnostic.push(line.to_string());
+                break;
+            } else if line.is_empty() && frames > 0 {
+                break;
+            }
+        }
+
+        Some(diagnostic.join("\n"))
+    }
+
+    fn valgrind_diagnostic(stderr: &str) -> Option<String> {
+        let diagnostic: Vec<String> = stderr
+            .lines()
+            .filter(|l| l.starts_with("=="))
+            .map(strip_pid)
+            .skip_while(|l| l.trim().is_empty())
+            .take_while(|l| !l.trim().is_empty())
+            .take(MAX_DIAGNOSTIC_LINES)
+            .collect();
+
+        if diagnostic.is_empty() {
+            None
+        } else {
+            Some(diagnostic.join("\n"))
+        }
+    }
+}
+
+// `==4242==ERROR: AddressSanitizer: ...`, not just any line the program prints.
+fn is_sanitizer_report(line: &str) -> bool {
+    let rest = match line.strip_prefix("==") {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    match rest.find("==") {
+        Some(end) => {
+            let pid = &rest[..end];
+            let report = &rest[end + 2..];
+
+            !pid.is_empty()
+                && pid.chars().all(|c| c.is_ascii_digit())
+                && report.starts_with("ERROR: ")
+                && report.contains("Sanitizer")
+        }
+        None => false,
+    }
+}
+
+// `main.cpp:4:7: runtime error: ...`, prefixed with the source location.
+fn is_undefined_behavior_report(line: &str) -> bool {
+    let location = match line.find(": runtime error: ") {
+        Some(end) => &line[..end],
+        None => return false,
+    };
+    let mut parts = location.rsplitn(3, ':');
+
+    let column = parts.next().map(|c| c.parse::<u32>().is_ok());
+    let row = parts.next().map(|r| r.parse::<u32>().is_ok());
+
+    column == Some(true) && row == Some(true) && parts.next().is_some()
+}
+
+// Sources are compiled in a scratch directory and binaries kept in the build
+// cache, neither of which is shown to users.
+fn strip_paths(diagnostic: &str, binary: &Path) -> String {
+    let mut diagnostic = match binary.parent() {
+        Some(cache) => diagnostic.replace(&format!("{}/", cache.to_string_lossy()), ""),
+        None => diagnostic.to_string(),
+    };
+    let scratch = env::temp_dir()
+        .join(SCRATCH_PREFIX)
+        .to_string_lossy()
+        .to_string();
+
+    while let Some(start) = diagnostic.find(&scratch) {
+        let name = start + scratch.len();
+        let end = diagnostic[name..]
+            .find('/')
+            .map_or(diagnostic.len(), |end| name + end + 1);
+        diagnostic.replace_range(start..end, "");
+    }
+
+    diagnostic
+}
+
+fn strip_pid(line: &str) -> String {
+    match line.strip_prefix("==") {
+        Some(rest) => match rest.find("==") {
+            Some(end) => rest[end + 2..].trim_start().to_string(),
+            None => line.to_string(),
+        },
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::compiler::Language;
+    use crate::runners::runner_error::RunnerError;
+    use crate::runners::sanitizer::{strip_paths, Sanitizer, SanitizerMode};
+    use std::env;
+    use std::path::Path;
+
+    #[test]
+    fn should_condense_address_sanitizer_report() {
+        let stderr = "=================================================================
+==4242==ERROR: AddressSanitizer: heap-buffer-overflow on address 0x602000000018
+READ of size 4 at 0x602000000018 thread T0
+    #0 0x4011d6 in main main.cpp:6
+    #1 0x7f0b1 in __libc_start_main
+    #2 0x4010ad in _start
+
+SUMMARY: AddressSanitizer: heap-buffer-overflow main.cpp:6 in main
+";
+
+        assert_eq!(
+            Sanitizer::sanitizer_diagnostic(stderr).unwrap(),
+            "ERROR: AddressSanitizer: heap-buffer-overflow on address 0x602000000018
+#0 0x4011d6 in main main.cpp:6
+#1 0x7f0b1 in __libc_start_main
+#2 0x4010ad in _start"
+        );
+    }
+
+    #[test]
+    fn should_hide_build_paths() {
+        let temp = env::temp_dir().to_string_lossy().to_string();
+        let diagnostic = format!(
+            "#0 0x4011d6 in main {0}/janosik-run-a1b2/main.cpp:6\n#3 0x10e0 in _start ({0}/janosik-builds-0/f00d+0x10e0)",
+            temp
+        );
+        let binary = Path::new(&temp).join("janosik-builds-0").join("f00d");
+
+        assert_eq!(
+            strip_paths(&diagnostic, &binary),
+            "#0 0x4011d6 in main main.cpp:6\n#3 0x10e0 in _start (f00d+0x10e0)"
+        );
+    }
+
+    #[test]
+    fn should_report_heap_overflow_without_paths() {
+        let source = "#include <stdlib.h>\nint main() { int *a = malloc(8); return a[2]; }\n";
+
+        let diagnostic = match Sanitizer::run(source, Language::C, "", SanitizerMode::Sanitizers) {
+            Err(RunnerError::MemoryError(diagnostic)) => diagnostic,
+            other => panic!("unexpected {:?}", other),
+        };
+
+        assert!(diagnostic.contains("main.c:2"), "{}", diagnostic);
+        assert!(!diagnostic.contains(&*env::temp_dir().to_string_lossy()));
+    }
+
+    #[test]
+    fn should_find_undefined_behavior() {
+        let stderr = "main.cpp:4:7: runtime error: signed integer overflow\n";
+
+        assert_eq!(
+            Sanitizer::sanitizer_diagnostic(stderr).unwrap(),
+            "main.cpp:4:7: runtime error: signed integer overflow"
+        );
+    }
+
+    #[test]
+    fn should_take_first_valgrind_error() {
+        let stderr = "==77== Invalid read of size 4
+==77==    at 0x109156: main (main.cpp:6)
+==77==
+==77== Conditional jump or move depends on uninitialised value(s)
+";
+
+        assert_eq!(
+            Sanitizer::valgrind_diagnostic(stderr).unwrap(),
+            "Invalid read of size 4\nat 0x109156: main (main.cpp:6)"
+        );
+    }
+
+    #[test]
+    fn should_ignore_clean_output() {
+        assert_eq!(Sanitizer::sanitizer_diagnostic("all good\n"), None);
+        assert_eq!(
+            Sanitizer::sanitizer_diagnostic("ERROR: wrong\nanswer: runtime error: none\n"),
+            None
+        );
+        assert_eq!(Sanitizer::valgrind_diagnostic(""), None);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::runners::runner_error::RunnerError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, DirBuilder, File};
+use std::io::{self, Read};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::warn;
+
+pub type Environment = BTreeMap<String, String>;
+
+const PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+const LOCALE: &str = "C.UTF-8";
+const CREATE_ATTEMPTS: usize = 8;
+pub const SCRATCH_PREFIX: &str = "janosik-run-";
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NamedFile {
+    pub name: String,
+    pub content: Vec<u8>,
+}
+
+// Private working directory of a single run, removed when dropped.
+pub struct ScratchDirectory {
+    path: PathBuf,
+}
+
+impl ScratchDirectory {
+    // The name is random and the directory must not exist yet, so another
+    // local user cannot prepare it in advance.
+    pub fn create() -> Result<ScratchDirectory, RunnerError> {
+        for _ in 0..CREATE_ATTEMPTS {
+            let path = match random_name() {
+                Ok(name) => env::temp_dir().join(format!("{}{}", SCRATCH_PREFIX, name)),
+                Err(_) => break,
+            };
+
+            match DirBuilder::new().mode(0o700).create(&path) {
+                Ok(()) => return Ok(ScratchDirectory { path }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(_) => break,
+            }
+        }
+
+        Err(RunnerError::Other(
+            "cannot create scratch directory".to_string(),
+        ))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&self, filename: &str, content: &str) -> Result<PathBuf, RunnerError> {
+        let path = self.path.join(filename);
+
+        match fs::write(&path, content) {
+            Ok(_) => Ok(path),
+            Err(_) => Err(RunnerError::Other(format!("cannot write {}", filename))),
+        }
+    }
+
+    pub fn write_files(&self, files: &[NamedFile]) -> Result<(), RunnerError> {
+        for file in files {
+            if !ScratchDirectory::is_plain_name(&file.name)
+                || fs::write(self.path.join(&file.name), &file.content).is_err()
+            {
+                return Err(RunnerError::Other(format!("cannot write {}", file.name)));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn collect_files(&self, names: &[String]) -> Vec<NamedFile> {
+        names
+            .iter()
+            .filter(|name| ScratchDirectory::is_plain_name(name))
+            .filter_map(|name| {
+                let content = fs::read(self.path.join(name)).ok()?;
+
+                Some(NamedFile {
+                    name: name.clone(),
+                    content,
+                })
+            })
+            .collect()
+    }
+
+    fn is_plain_name(name: &str) -> bool {
+        !name.is_empty() && !name.contains('/') && name != "." && name != ".."
+    }
+
+    // Runs the command in this directory with only a fixed set of variables,
+    // so that nothing from the bot's own environment leaks into the program.
+    pub fn prepare<'a>(
+        &self,
+        command: &'a mut Command,
+        environment: &Environment,
+    ) -> &'a mut Command {
+        command
+            .env_clear()
+            .env("PATH", PATH)
+            .env("LANG", LOCALE)
+            .env("LC_ALL", LOCALE)
+            .env("HOME", &self.path)
+            .env("TMPDIR", &self.path)
+            .envs(environment)
+            .current_dir(&self.path)
+    }
+}
+
+impl Drop for ScratchDirectory {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            warn!("Cannot remove scratch directory {:?}: {}", self.path, e);
+        }
+    }
+}
+
+pub fn random_name() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::scratch_directory::{Environment, NamedFile, ScratchDirectory};
+    use std::os::unix::fs::PermissionsExt;
+    use std::process::Command;
+
+    #[test]
+    fn should_not_inherit_environment() {
+        std::env::set_var("JANOSIK_TOKEN", "secret");
+        let scratch = ScratchDirectory::create().unwrap();
+        let mut environment = Environment::new();
+        environment.insert("TASK".to_string(), "value".to_string());
+
+        let output = scratch
+            .prepare(&mut Command::new("env"), &environment)
+            .output()
+            .unwrap();
+        let output = String::from_utf8_lossy(&output.stdout);
+
+        assert!(!output.contains("JANOSIK_TOKEN"));
+        assert!(output.contains("TASK=value"));
+        assert!(output.contains(&format!("HOME={}", scratch.path().display())));
+    }
+
+    #[test]
+    fn should_collect_written_files() {
+        let scratch = ScratchDirectory::create().unwrap();
+        let file = NamedFile {
+            name: "input.txt".to_string(),
+            content: b"1 2".to_vec(),
+        };
+
+        scratch.write_files(std::slice::from_ref(&file)).unwrap();
+        let names = vec!["input.txt".to_string(), "output.txt".to_string()];
+
+        assert_eq!(scratch.collect_files(&names), vec![file]);
+    }
+
+    #[test]
+    fn should_reject_paths_outside_directory() {
+        let scratch = ScratchDirectory::create().unwrap();
+        let file = NamedFile {
+            name: "../escaped.txt".to_string(),
+            content: Vec::new(),
+        };
+
+        assert!(scratch.write_files(&[file]).is_err());
+    }
+
+    #[test]
+    fn should_create_private_directory() {
+        let scratch = ScratchDirectory::create().unwrap();
+        let mode = scratch.path().metadata().unwrap().permissions().mode();
+
+        assert_eq!(mode & 0o777, 0o700);
+        assert_ne!(ScratchDirectory::create().unwrap().path(), scratch.path());
+    }
+
+    #[test]
+    fn should_remove_directory_when_dropped() {
+        let scratch = ScratchDirectory::create().unwrap();
+        let path = scratch.path().to_path_buf();
+
+        drop(scratch);
+
+        assert!(!path.exists());
+    }
+}
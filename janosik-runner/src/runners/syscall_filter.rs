@@ -0,0 +1,479 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use tracing::warn;
+
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH: u32 = 0xc000_003e;
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH: u32 = 0xc000_00b7;
+
+// Offsets in struct seccomp_data, the low half of an argument on little endian.
+const NR_OFFSET: u32 = 0;
+const ARCH_OFFSET: u32 = 4;
+const FIRST_ARG_OFFSET: u32 = 16;
+
+// Syscalls of the x32 ABI, which use the same architecture, have this bit set.
+#[cfg(target_arch = "x86_64")]
+const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+const CLONE_NAMESPACES: libc::c_int = libc::CLONE_NEWNS
+    | libc::CLONE_NEWCGROUP
+    | libc::CLONE_NEWUTS
+    | libc::CLONE_NEWIPC
+    | libc::CLONE_NEWUSER
+    | libc::CLONE_NEWPID
+    | libc::CLONE_NEWNET;
+
+// _LINUX_CAPABILITY_VERSION_3, which takes two sets of 32 capabilities.
+const CAPABILITY_VERSION: u32 = 0x2008_0522;
+
+const KILL: u32 = libc::SECCOMP_RET_KILL_PROCESS;
+const ALLOW: u32 = libc::SECCOMP_RET_ALLOW;
+
+// Enough for statically and dynamically linked C and C++ programs, threads
+// included, but nothing that reaches outside of the process.
+const STRICT_ALLOWED: &[libc::c_long] = &[
+    libc::SYS_read,
+    libc::SYS_write,
+    libc::SYS_readv,
+    libc::SYS_writev,
+    libc::SYS_pread64,
+    libc::SYS_pwrite64,
+    libc::SYS_openat,
+    libc::SYS_close,
+    libc::SYS_fstat,
+    libc::SYS_newfstatat,
+    libc::SYS_statx,
+    libc::SYS_lseek,
+    libc::SYS_ftruncate,
+    libc::SYS_fsync,
+    libc::SYS_fdatasync,
+    libc::SYS_fcntl,
+    libc::SYS_ioctl,
+    libc::SYS_dup,
+    libc::SYS_dup3,
+    libc::SYS_pipe2,
+    libc::SYS_ppoll,
+    libc::SYS_pselect6,
+    libc::SYS_getdents64,
+    libc::SYS_faccessat,
+    libc::SYS_faccessat2,
+    libc::SYS_readlinkat,
+    libc::SYS_getcwd,
+    libc::SYS_mmap,
+    libc::SYS_mprotect,
+    libc::SYS_munmap,
+    libc::SYS_mremap,
+    libc::SYS_madvise,
+    libc::SYS_msync,
+    libc::SYS_brk,
+    libc::SYS_rt_sigaction,
+    libc::SYS_rt_sigprocmask,
+    libc::SYS_rt_sigreturn,
+    libc::SYS_sigaltstack,
+    libc::SYS_futex,
+    libc::SYS_set_tid_address,
+    libc::SYS_set_robust_list,
+    libc::SYS_rseq,
+    libc::SYS_membarrier,
+    libc::SYS_sched_yield,
+    libc::SYS_sched_getaffinity,
+    libc::SYS_prlimit64,
+    libc::SYS_getrusage,
+    libc::SYS_getrandom,
+    libc::SYS_clock_gettime,
+    libc::SYS_clock_getres,
+    libc::SYS_clock_nanosleep,
+    libc::SYS_nanosleep,
+    libc::SYS_gettimeofday,
+    libc::SYS_times,
+    libc::SYS_getpid,
+    libc::SYS_gettid,
+    libc::SYS_getppid,
+    libc::SYS_getuid,
+    libc::SYS_geteuid,
+    libc::SYS_getgid,
+    libc::SYS_getegid,
+    libc::SYS_uname,
+    libc::SYS_sysinfo,
+    libc::SYS_execve,
+    libc::SYS_exit,
+    libc::SYS_exit_group,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_arch_prctl,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_open,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_stat,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_lstat,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_access,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_readlink,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_dup2,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_pipe,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_poll,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_select,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_time,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_getrlimit,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_setrlimit,
+];
+
+// Trusted programs may do anything but touch the system or other processes.
+const RELAXED_BLOCKED: &[libc::c_long] = &[
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_socket,
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_chroot,
+    libc::SYS_setns,
+    libc::SYS_unshare,
+    libc::SYS_kexec_load,
+    libc::SYS_kexec_file_load,
+    libc::SYS_reboot,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_acct,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_userfaultfd,
+    libc::SYS_io_uring_setup,
+    libc::SYS_open_by_handle_at,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_settimeofday,
+    libc::SYS_clock_settime,
+    libc::SYS_adjtimex,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_iopl,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_ioperm,
+];
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyscallPolicy {
+    // Only what a single C or C++ program needs, for submitted code.
+    Strict,
+    // Everything except syscalls dangerous for the host, for reference binaries.
+    Relaxed,
+    // No filter, for compilers and other tools.
+    Unrestricted,
+}
+
+#[repr(C)]
+struct CapabilityHeader {
+    version: u32,
+    pid: libc::c_int,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CapabilityData {
+    effective: u32,
+    permitted: u32,
+    inheritable: u32,
+}
+
+pub struct SyscallFilter {
+    program: Vec<libc::sock_filter>,
+    // The tgkill target has to be the program itself, known only after fork.
+    pid_index: Option<usize>,
+}
+
+impl SyscallFilter {
+    // The filter is installed by the last pre_exec hook, so nothing set up
+    // before exec is filtered.
+    pub fn apply(command: &mut Command, policy: SyscallPolicy) -> &mut Command {
+        let mut filter = match SyscallFilter::build(policy) {
+            Some(filter) => filter,
+            None => return command,
+        };
+
+        unsafe { command.pre_exec(move || filter.install()) }
+    }
+
+    // Filtered programs run as the bot's user, which could read the tokens in
+    // /proc/<pid>/environ of the bot. Without being dumpable only a process
+    // with CAP_SYS_PTRACE can, and filtered programs have no capabilities.
+    pub fn protect_parent() -> io::Result<()> {
+        if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    fn build(policy: SyscallPolicy) -> Option<SyscallFilter> {
+        if cfg!(not(any(target_arch = "x86_64", target_arch = "aarch64"))) {
+            if policy != SyscallPolicy::Unrestricted {
+                warn!("Syscall filtering is not supported on this architecture");
+            }
+            return None;
+        }
+
+        let mut filter = SyscallFilter {
+            program: Vec::new(),
+            pid_index: None,
+        };
+
+        match policy {
+            SyscallPolicy::Strict => filter.strict(),
+            SyscallPolicy::Relaxed => filter.relaxed(),
+            SyscallPolicy::Unrestricted => return None,
+        }
+
+        Some(filter)
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    fn check_architecture(&mut self) {
+        self.load(ARCH_OFFSET);
+        self.jump(libc::BPF_JEQ, AUDIT_ARCH, 1, 0);
+        self.ret(KILL);
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    fn check_architecture(&mut self) {}
+
+    // Expects the syscall number in the accumulator.
+    #[cfg(target_arch = "x86_64")]
+    fn block_x32(&mut self) {
+        self.jump(libc::BPF_JGE, X32_SYSCALL_BIT, 0, 1);
+        self.ret(KILL);
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    fn block_x32(&mut self) {}
+
+    // glibc falls back to clone when clone3, whose flags cannot be
+    // inspected, is not available.
+    fn refuse_clone3(&mut self) {
+        self.return_if(
+            libc::SYS_clone3,
+            libc::SECCOMP_RET_ERRNO | libc::ENOSYS as u32,
+        );
+    }
+
+    fn strict(&mut self) {
+        self.check_architecture();
+        self.load(NR_OFFSET);
+
+        for &syscall in STRICT_ALLOWED {
+            self.return_if(syscall, ALLOW);
+        }
+
+        self.refuse_clone3();
+
+        // Threads only, no new processes.
+        self.return_if_first_arg(
+            libc::SYS_clone,
+            libc::BPF_JSET,
+            libc::CLONE_THREAD as u32,
+            ALLOW,
+        );
+
+        // abort() signals the calling thread.
+        self.return_if_first_arg(libc::SYS_tgkill, libc::BPF_JEQ, 0, ALLOW);
+        self.pid_index = Some(self.program.len() - 3);
+
+        self.ret(KILL);
+    }
+
+    fn relaxed(&mut self) {
+        self.check_architecture();
+        self.load(NR_OFFSET);
+        self.block_x32();
+
+        for &syscall in RELAXED_BLOCKED {
+            self.return_if(syscall, KILL);
+        }
+
+        // New processes, but not in new namespaces.
+        self.refuse_clone3();
+        self.return_if_first_arg(
+            libc::SYS_clone,
+            libc::BPF_JSET,
+            CLONE_NAMESPACES as u32,
+            KILL,
+        );
+
+        self.ret(ALLOW);
+    }
+
+    fn return_if(&mut self, syscall: libc::c_long, action: u32) {
+        self.jump(libc::BPF_JEQ, syscall as u32, 0, 1);
+        self.ret(action);
+    }
+
+    // Leaves the syscall number in the accumulator when it does not return.
+    fn return_if_first_arg(&mut self, syscall: libc::c_long, test: u32, value: u32, action: u32) {
+        self.jump(libc::BPF_JEQ, syscall as u32, 0, 3);
+        self.load(FIRST_ARG_OFFSET);
+        self.jump(test, value, 0, 1);
+        self.ret(action);
+        self.load(NR_OFFSET);
+    }
+
+    fn load(&mut self, offset: u32) {
+        self.push(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, offset, 0, 0);
+    }
+
+    fn jump(&mut self, test: u32, value: u32, if_true: u8, if_false: u8) {
+        self.push(libc::BPF_JMP | test | libc::BPF_K, value, if_true, if_false);
+    }
+
+    fn ret(&mut self, action: u32) {
+        self.push(libc::BPF_RET | libc::BPF_K, action, 0, 0);
+    }
+
+    fn push(&mut self, code: u32, k: u32, jt: u8, jf: u8) {
+        self.program.push(libc::sock_filter {
+            code: code as u16,
+            jt,
+            jf,
+            k,
+        });
+    }
+
+    // Runs between fork and exec, so it must not allocate.
+    fn install(&mut self) -> io::Result<()> {
+        if let Some(index) = self.pid_index {
+            self.program[index].k = unsafe { libc::getpid() } as u32;
+        }
+
+        let program = libc::sock_fprog {
+            len: self.program.len() as u16,
+            filter: self.program.as_mut_ptr(),
+        };
+
+        // Together with no_new_privs, exec cannot give the capabilities back,
+        // not even to root.
+        let header = CapabilityHeader {
+            version: CAPABILITY_VERSION,
+            pid: 0,
+        };
+        let none = [CapabilityData {
+            effective: 0,
+            permitted: 0,
+            inheritable: 0,
+        }; 2];
+
+        unsafe {
+            if libc::syscall(libc::SYS_capset, &header, none.as_ptr()) != 0
+                || libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0
+                || libc::prctl(
+                    libc::PR_SET_SECCOMP,
+                    libc::SECCOMP_MODE_FILTER,
+                    &program as *const libc::sock_fprog,
+                ) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::syscall_filter::{SyscallFilter, SyscallPolicy};
+    use std::os::unix::process::{CommandExt, ExitStatusExt};
+    use std::process::Command;
+
+    fn run(script: &str, policy: SyscallPolicy) -> Option<i32> {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+
+        SyscallFilter::apply(&mut command, policy)
+            .status()
+            .unwrap()
+            .signal()
+    }
+
+    #[test]
+    fn should_only_block_dangerous_syscalls_when_relaxed() {
+        assert_eq!(run("ls / > /dev/null", SyscallPolicy::Relaxed), None);
+        assert_eq!(run("ls / | cat > /dev/null", SyscallPolicy::Relaxed), None);
+    }
+
+    // The hook runs after the one installing the filter.
+    fn run_syscall_after_filter(syscall: libc::c_long, first_arg: libc::c_long) -> Option<i32> {
+        let mut command = Command::new("true");
+        SyscallFilter::apply(&mut command, SyscallPolicy::Relaxed);
+
+        unsafe {
+            command.pre_exec(move || {
+                if libc::syscall(syscall, first_arg, 0, 0, 0, 0) == 0 && syscall == libc::SYS_clone
+                {
+                    libc::_exit(0);
+                }
+                Ok(())
+            });
+        }
+
+        command.status().unwrap().signal()
+    }
+
+    #[test]
+    fn should_block_namespaces_when_relaxed() {
+        let namespace = (libc::CLONE_NEWUSER | libc::SIGCHLD) as libc::c_long;
+
+        assert_eq!(
+            run_syscall_after_filter(libc::SYS_clone, namespace),
+            Some(libc::SIGSYS)
+        );
+        assert_eq!(
+            run_syscall_after_filter(libc::SYS_io_uring_setup, 1),
+            Some(libc::SIGSYS)
+        );
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn should_block_x32_syscalls_when_relaxed() {
+        let getpid = 0x4000_0000 | libc::SYS_getpid;
+
+        assert_eq!(run_syscall_after_filter(getpid, 0), Some(libc::SIGSYS));
+    }
+
+    #[test]
+    fn should_hide_parent_environment_when_strict() {
+        SyscallFilter::protect_parent().unwrap();
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg("exec 3< /proc/$PPID/environ && echo readable");
+
+        let output = SyscallFilter::apply(&mut command, SyscallPolicy::Strict)
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.signal(), None);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    }
+
+    #[test]
+    fn should_block_new_processes_when_strict() {
+        assert_eq!(run("exit 0", SyscallPolicy::Strict), None);
+        assert_eq!(run("ls; exit 0", SyscallPolicy::Strict), Some(libc::SIGSYS));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::runners::binary_runner::{BinaryRunner, ProcessOptions};
+use crate::runners::runner_error::RunnerError;
+use std::process::Command;
+use std::time::Duration;
+use tracing::info;
+
+const VALIDATOR_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+pub struct Validator {}
+
+impl Validator {
+    pub fn validate(program_name: &str, input: &str) -> Result<(), RunnerError> {
+        let validator_name = format!("{}.validator", program_name);
+
+        let path = match BinaryRunner::binary_path(&validator_name) {
+            Ok(path) => path,
+            Err(_) => return Ok(()),
+        };
+
+        Validator::check(&mut Command::new(path), &validator_name, input)
+    }
+
+    // The input is given as a file and the output is read while the validator
+    // runs, so one that stops reading early or writes a lot cannot hang it.
+    fn check(command: &mut Command, validator_name: &str, input: &str) -> Result<(), RunnerError> {
+        let options = ProcessOptions {
+            time_limit: VALIDATOR_TIME_LIMIT,
+            ..ProcessOptions::default()
+        };
+
+        let process = match BinaryRunner::execute_command_with(command, input, &options) {
+            Ok(process) => process,
+            Err(RunnerError::Timeout) => {
+                return Err(RunnerError::Other("validator timed out".to_string()))
+            }
+            Err(e) => return Err(e),
+        };
+
+        info!("{} returned {}", validator_name, process.status_code);
+
+        if process.status_code == 0 {
+            return Ok(());
+        }
+
+        Err(RunnerError::InvalidInput(process.stderr.trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::runner_error::RunnerError;
+    use crate::runners::validator::Validator;
+    use std::process::Command;
+
+    fn check(script: &str, input: &str) -> Result<(), RunnerError> {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+
+        Validator::check(&mut command, "test.validator", input)
+    }
+
+    #[test]
+    fn should_not_wait_for_unread_input() {
+        let input = "1 ".repeat(1 << 20);
+
+        assert_eq!(
+            check("echo 'n is too big' >&2; exit 1", &input),
+            Err(RunnerError::InvalidInput("n is too big".to_string()))
+        );
+        assert_eq!(
+            check("sleep 30", &input),
+            Err(RunnerError::Other("validator timed out".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_read_long_explanations() {
+        let out = check("head -c 1000000 /dev/zero | tr '\\0' x >&2; exit 1", "1\n");
+
+        match out {
+            Err(RunnerError::InvalidInput(explanation)) => assert_eq!(explanation.len(), 1000000),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::runners::runner_error::RunnerError;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tracing::error;
+
+const ASSIGNMENTS_FILENAME: &str = "assignments.toml";
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LatePolicy {
+    // Submissions after the deadline are not judged.
+    #[default]
+    Reject,
+    // Submissions after the deadline are judged and marked as late.
+    Mark,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Assignment {
+    pub name: String,
+    pub tasks: Vec<String>,
+    pub opens: Option<DateTime<FixedOffset>>,
+    pub closes: DateTime<FixedOffset>,
+    #[serde(default)]
+    pub late: LatePolicy,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Deadline {
+    // The task is in no assignment and can always be submitted.
+    None,
+    NotOpen(DateTime<FixedOffset>),
+    Open(DateTime<FixedOffset>),
+    Late(DateTime<FixedOffset>),
+    Closed(DateTime<FixedOffset>),
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Assignments {
+    #[serde(default)]
+    pub assignments: Vec<Assignment>,
+}
+
+impl Assignments {
+    // An invalid file is an error rather than no deadlines, so that a typo
+    // does not open every assignment.
+    pub fn load() -> Result<Assignments, RunnerError> {
+        let path = Assignments::path();
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(_) => return Ok(Assignments::default()),
+        };
+
+        toml::from_str(&content).map_err(|e| {
+            error!("Invalid assignments {:?}: {}", path, e);
+            RunnerError::InvalidConfig(format!("{}: {}", ASSIGNMENTS_FILENAME, e))
+        })
+    }
+
+    fn path() -> PathBuf {
+        PathBuf::from("tasks").join(ASSIGNMENTS_FILENAME)
+    }
+
+    // A task in several assignments takes the latest deadline.
+    pub fn deadline(&self, task: &str, now: DateTime<Utc>) -> Deadline {
+        let assignment = self
+            .assignments
+            .iter()
+            .filter(|a| a.tasks.iter().any(|t| t == task))
+            .max_by_key(|a| a.closes);
+
+        let assignment = match assignment {
+            Some(assignment) => assignment,
+            None => return Deadline::None,
+        };
+
+        match assignment.opens {
+            Some(opens) if now < opens => Deadline::NotOpen(opens),
+            _ if now <= assignment.closes => Deadline::Open(assignment.closes),
+            _ if assignment.late == LatePolicy::Mark => Deadline::Late(assignment.closes),
+            _ => Deadline::Closed(assignment.closes),
+        }
+    }
+
+    // Whether something submitted at `time` came after the task's deadline.
+    pub fn is_late(&self, task: &str, time: DateTime<Utc>) -> bool {
+        matches!(
+            self.deadline(task, time),
+            Deadline::Late(_) | Deadline::Closed(_)
+        )
+    }
+
+    // Assignments still to be closed, the nearest deadline first.
+    pub fn upcoming(&self, now: DateTime<Utc>) -> Vec<&Assignment> {
+        let mut upcoming: Vec<&Assignment> =
+            self.assignments.iter().filter(|a| a.closes > now).collect();
+        upcoming.sort_by_key(|a| a.closes);
+
+        upcoming
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tasks::assignment::{Assignments, Deadline};
+    use chrono::{DateTime, Utc};
+
+    const ASSIGNMENTS: &str = r#"
+        [[assignments]]
+        name = "Lista 1"
+        tasks = ["sum"]
+        opens = "2020-10-01T08:00:00+02:00"
+        closes = "2020-10-15T23:59:00+02:00"
+
+        [[assignments]]
+        name = "Lista 2"
+        tasks = ["sort"]
+        closes = "2020-10-29T23:59:00+01:00"
+        late = "mark"
+    "#;
+
+    fn time(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn should_check_deadlines() {
+        let assignments: Assignments = toml::from_str(ASSIGNMENTS).unwrap();
+        let opens = DateTime::parse_from_rfc3339("2020-10-01T08:00:00+02:00").unwrap();
+        let closes = DateTime::parse_from_rfc3339("2020-10-15T23:59:00+02:00").unwrap();
+
+        assert_eq!(
+            assignments.deadline("sum", time("2020-10-01T05:00:00Z")),
+            Deadline::NotOpen(opens)
+        );
+        assert_eq!(
+            assignments.deadline("sum", time("2020-10-15T21:00:00Z")),
+            Deadline::Open(closes)
+        );
+        assert_eq!(
+            assignments.deadline("sum", time("2020-10-15T22:00:00Z")),
+            Deadline::Closed(closes)
+        );
+        assert!(matches!(
+            assignments.deadline("sort", time("2020-11-01T00:00:00Z")),
+            Deadline::Late(_)
+        ));
+        assert_eq!(
+            assignments.deadline("other", time("2020-11-01T00:00:00Z")),
+            Deadline::None
+        );
+    }
+
+    #[test]
+    fn should_check_lateness_at_submission_time() {
+        let assignments: Assignments = toml::from_str(ASSIGNMENTS).unwrap();
+
+        assert!(!assignments.is_late("sum", time("2020-10-15T21:00:00Z")));
+        assert!(assignments.is_late("sum", time("2020-10-15T22:00:00Z")));
+        assert!(assignments.is_late("sort", time("2020-11-01T00:00:00Z")));
+        assert!(!assignments.is_late("other", time("2020-11-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn should_list_upcoming_deadlines() {
+        let assignments: Assignments = toml::from_str(ASSIGNMENTS).unwrap();
+
+        let names: Vec<&str> = assignments
+            .upcoming(time("2020-10-10T00:00:00Z"))
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["Lista 1", "Lista 2"]);
+        assert_eq!(assignments.upcoming(time("2020-10-20T00:00:00Z")).len(), 1);
+    }
+}
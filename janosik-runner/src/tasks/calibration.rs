@@ -0,0 +1,130 @@
+use crate::runners::binary_runner::{BinaryRunner, ProcessOptions, MAX_TIME_LIMIT};
+use crate::runners::runner_error::RunnerError;
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::test_suite::TestSuite;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::time::Duration;
+use tracing::info;
+
+pub const DEFAULT_MULTIPLIER: f64 = 3.0;
+pub const DEFAULT_FLOOR: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Calibration {
+    pub tests: usize,
+    pub worst_test: String,
+    pub worst_time: Duration,
+    pub time_limit: Duration,
+}
+
+pub struct Calibrator {}
+
+impl Calibrator {
+    // Runs the reference solution on every test of the task with the default
+    // time limit and proposes a multiple of its worst time as the task's limit.
+    // It is measured where submissions run, see `Calibration::save`.
+    pub fn calibrate(
+        task: &str,
+        multiplier: f64,
+        floor: Duration,
+    ) -> Result<Calibration, RunnerError> {
+        if !is_valid_multiplier(multiplier) {
+            return Err(RunnerError::Other(format!(
+                "invalid multiplier {}",
+                multiplier
+            )));
+        }
+
+        let config = TaskConfig::try_load(task)?;
+        let suite = TestSuite::load(task, &config)?;
+        let reference = config.reference_binary(task);
+        let options = ProcessOptions {
+            environment: config.environment.clone(),
+            syscalls: config.reference_syscalls(),
+            ..ProcessOptions::default()
+        };
+
+        let mut times = Vec::new();
+
+        for test in &suite.tests {
+            let path = BinaryRunner::binary_path(&reference).map_err(|_| RunnerError::NotFound)?;
+            let process =
+                BinaryRunner::execute_command_with(&mut Command::new(path), &test.input, &options)?;
+
+            if process.status_code != 0 {
+                return Err(RunnerError::Crash);
+            }
+
+            let time = process.usage.wall_time;
+            info!("{} took {:?} on test {}", reference, time, test.name);
+
+            times.push((test.name.clone(), time));
+        }
+
+        let (worst_test, worst_time) = times
+            .into_iter()
+            .max_by_key(|(_, time)| *time)
+            .ok_or_else(|| RunnerError::Other(format!("no tests of task '{}'", task)))?;
+
+        Ok(Calibration {
+            tests: suite.tests.len(),
+            worst_test,
+            worst_time,
+            time_limit: time_limit(worst_time, multiplier, floor),
+        })
+    }
+}
+
+impl Calibration {
+    pub fn save(&self, task: &str) -> Result<(), RunnerError> {
+        TaskConfig::save_time_limit(task, self.time_limit)
+    }
+}
+
+pub fn is_valid_multiplier(multiplier: f64) -> bool {
+    multiplier.is_finite() && multiplier > 0.0
+}
+
+pub fn time_limit(worst_time: Duration, multiplier: f64, floor: Duration) -> Duration {
+    let millis = (worst_time.as_secs_f64() * multiplier * 1000.0).ceil() as u64;
+
+    Duration::from_millis(millis).max(floor).min(MAX_TIME_LIMIT)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::binary_runner::MAX_TIME_LIMIT;
+    use crate::tasks::calibration::{is_valid_multiplier, time_limit};
+    use std::time::Duration;
+
+    #[test]
+    fn should_multiply_worst_time() {
+        let limit = time_limit(Duration::from_millis(400), 2.5, Duration::from_millis(200));
+
+        assert_eq!(limit, Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn should_not_go_below_floor() {
+        let limit = time_limit(Duration::from_millis(3), 3.0, Duration::from_millis(200));
+
+        assert_eq!(limit, Duration::from_millis(200));
+    }
+
+    #[test]
+    fn should_cap_limit() {
+        let limit = time_limit(Duration::from_secs(50), 3.0, Duration::from_millis(200));
+
+        assert_eq!(limit, MAX_TIME_LIMIT);
+    }
+
+    #[test]
+    fn should_reject_invalid_multipliers() {
+        assert!(is_valid_multiplier(1.5));
+        assert!(!is_valid_multiplier(0.0));
+        assert!(!is_valid_multiplier(-2.0));
+        assert!(!is_valid_multiplier(f64::NAN));
+        assert!(!is_valid_multiplier(f64::INFINITY));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::{Environment, ScratchDirectory};
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::test_suite::TestCase;
+use crate::tasks::verdict::Verdict;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::process::Command;
+use tracing::{info, warn};
+
+const KATTIS_ACCEPTED: i32 = 42;
+const KATTIS_WRONG_ANSWER: i32 = 43;
+
+// Argument order and exit codes expected from the task's checker.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckerInterface {
+    // `checker <input> <output> <answer>`, exit code 0 accepts.
+    #[default]
+    Testlib,
+    // `checker <input> <answer> <feedback dir> < output`, exit code 42 accepts.
+    Kattis,
+}
+
+pub fn check(config: &TaskConfig, test: &TestCase, output: &str) -> Verdict {
+    let checker = match &config.checker {
+        Some(checker) => checker,
+        None if outputs_match(&test.expected, output) => return Verdict::Accepted,
+        None => return Verdict::WrongAnswer,
+    };
+
+    match run_checker(checker, config.checker_interface, test, output) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            warn!("Checker {} failed: {:?}", checker, e);
+            Verdict::InternalError
+        }
+    }
+}
+
+pub fn outputs_match(expected: &str, actual: &str) -> bool {
+    lines(expected) == lines(actual)
+}
+
+fn run_checker(
+    checker: &str,
+    interface: CheckerInterface,
+    test: &TestCase,
+    output: &str,
+) -> Result<Verdict, RunnerError> {
+    let scratch = ScratchDirectory::create()?;
+    let input = scratch.write("input.txt", &test.input)?;
+    let answer = scratch.write("answer.txt", &test.expected)?;
+    let output_path = scratch.write("output.txt", output)?;
+    let feedback = scratch.path().join("feedback");
+
+    if fs::create_dir(&feedback).is_err() {
+        return Err(RunnerError::Other(
+            "cannot create feedback directory".to_string(),
+        ));
+    }
+
+    let mut command =
+        Command::new(BinaryRunner::binary_path(checker).map_err(|_| RunnerError::NotFound)?);
+
+    let (stdin, accepted, wrong_answer) = match interface {
+        CheckerInterface::Testlib => {
+            command.arg(&input).arg(&output_path).arg(&answer);
+            ("", vec![0], vec![1, 2])
+        }
+        CheckerInterface::Kattis => {
+            command.arg(&input).arg(&answer).arg(&feedback);
+            (output, vec![KATTIS_ACCEPTED], vec![KATTIS_WRONG_ANSWER])
+        }
+    };
+
+    let process = BinaryRunner::execute_command(&mut command, stdin, &Environment::new())?;
+    info!("Checker {} returned {}", checker, process.status_code);
+
+    if accepted.contains(&process.status_code) {
+        Ok(Verdict::Accepted)
+    } else if wrong_answer.contains(&process.status_code) {
+        Ok(Verdict::WrongAnswer)
+    } else {
+        Err(RunnerError::Other(process.stderr))
+    }
+}
+
+fn lines(output: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = output.lines().map(|l| l.trim_end()).collect();
+
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tasks::checker::outputs_match;
+
+    #[test]
+    fn should_ignore_trailing_whitespace() {
+        assert!(outputs_match("1 2\n3\n", "1 2  \n3\n\n"));
+        assert!(!outputs_match("1 2\n3\n", "1 2 3\n"));
+    }
+}
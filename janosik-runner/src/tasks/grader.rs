@@ -0,0 +1,168 @@
+use crate::runners::binary_runner::{BinaryRunner, ProcessOptions};
+use crate::tasks::checker::check;
+use crate::tasks::scoring::SubtaskScore;
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::test_suite::{TestCase, TestSuite};
+use crate::tasks::verdict::Verdict;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use tracing::info;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TestResult {
+    pub test: String,
+    pub public: bool,
+    pub verdict: Verdict,
+    pub time_ms: u128,
+    pub memory_kb: u64,
+    // Sent from workers, but left out of reports.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Grade {
+    pub submission: String,
+    pub score: f64,
+    pub max_score: f64,
+    pub subtasks: Vec<SubtaskScore>,
+    pub tests: Vec<TestResult>,
+}
+
+impl Grade {
+    pub fn new(submission: &str, suite: &TestSuite, tests: Vec<TestResult>) -> Grade {
+        let subtasks: Vec<SubtaskScore> = suite
+            .subtasks
+            .iter()
+            .map(|subtask| subtask.score(&tests))
+            .collect();
+
+        Grade {
+            submission: submission.to_string(),
+            score: subtasks.iter().map(|s| s.points).sum(),
+            max_score: subtasks.iter().map(|s| s.max_points).sum(),
+            subtasks,
+            tests,
+        }
+    }
+
+    pub fn failed(submission: &str, suite: &TestSuite, verdict: Verdict) -> Grade {
+        let tests = suite
+            .tests
+            .iter()
+            .map(|test| TestResult {
+                test: test.name.clone(),
+                public: test.public,
+                verdict,
+                time_ms: 0,
+                memory_kb: 0,
+                output: String::new(),
+            })
+            .collect();
+
+        Grade::new(submission, suite, tests)
+    }
+
+    pub fn passed(&self) -> usize {
+        self.tests
+            .iter()
+            .filter(|t| t.verdict == Verdict::Accepted)
+            .count()
+    }
+
+    // The verdict of the first failed test.
+    pub fn verdict(&self) -> Verdict {
+        self.tests
+            .iter()
+            .map(|t| t.verdict)
+            .find(|&verdict| verdict != Verdict::Accepted)
+            .unwrap_or(Verdict::Accepted)
+    }
+
+    pub fn max_time_ms(&self) -> u128 {
+        self.tests.iter().map(|t| t.time_ms).max().unwrap_or(0)
+    }
+
+    pub fn max_memory_kb(&self) -> u64 {
+        self.tests.iter().map(|t| t.memory_kb).max().unwrap_or(0)
+    }
+}
+
+pub struct Grader {}
+
+impl Grader {
+    pub fn grade(
+        submission: &str,
+        executable: &Path,
+        suite: &TestSuite,
+        config: &TaskConfig,
+    ) -> Grade {
+        let options = ProcessOptions::for_submission(config);
+
+        Grader::grade_with(submission, executable, suite, config, &options)
+    }
+
+    // Reference solutions are trusted and run with the relaxed syscall policy.
+    pub fn grade_reference(
+        task: &str,
+        executable: &Path,
+        suite: &TestSuite,
+        config: &TaskConfig,
+    ) -> Grade {
+        let options = ProcessOptions::for_task(config);
+
+        Grader::grade_with(task, executable, suite, config, &options)
+    }
+
+    fn grade_with(
+        submission: &str,
+        executable: &Path,
+        suite: &TestSuite,
+        config: &TaskConfig,
+        options: &ProcessOptions,
+    ) -> Grade {
+        info!("Grading {} on task '{}'", submission, suite.task);
+
+        let tests: Vec<TestResult> = suite
+            .tests
+            .iter()
+            .map(|test| Grader::run_test(executable, test, config, options))
+            .collect();
+
+        Grade::new(submission, suite, tests)
+    }
+
+    fn run_test(
+        executable: &Path,
+        test: &TestCase,
+        config: &TaskConfig,
+        options: &ProcessOptions,
+    ) -> TestResult {
+        let result =
+            BinaryRunner::execute_command_with(&mut Command::new(executable), &test.input, options);
+
+        match result {
+            Ok(process) => TestResult {
+                test: test.name.clone(),
+                public: test.public,
+                verdict: if process.status_code != 0 {
+                    Verdict::RuntimeError
+                } else {
+                    check(config, test, &process.stdout)
+                },
+                time_ms: process.usage.wall_time.as_millis(),
+                memory_kb: process.usage.max_rss_kb,
+                output: process.stdout,
+            },
+            Err(e) => TestResult {
+                test: test.name.clone(),
+                public: test.public,
+                verdict: Verdict::from_error(&e),
+                time_ms: 0,
+                memory_kb: 0,
+                output: String::new(),
+            },
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::compiler::{Compiler, Language};
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::{Environment, ScratchDirectory};
+use crate::tasks::grader::Grader;
+use crate::tasks::package::{Package, PackageFormat};
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::task_directory;
+use crate::tasks::test_suite::TestSuite;
+use crate::tasks::verdict::Verdict;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+#[derive(Debug)]
+pub struct ImportReport {
+    pub format: PackageFormat,
+    pub tests: usize,
+    pub tests_without_answers: usize,
+    pub reference: Option<String>,
+    pub checker: Option<String>,
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+    pub failed_tests: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let none = || "none".to_string();
+
+        writeln!(f, "Format: {}", self.format)?;
+        writeln!(
+            f,
+            "Tests: {} ({} without answers)",
+            self.tests, self.tests_without_answers
+        )?;
+        writeln!(
+            f,
+            "Reference: {}",
+            self.reference.clone().unwrap_or_else(none)
+        )?;
+        writeln!(f, "Checker: {}", self.checker.clone().unwrap_or_else(none))?;
+        writeln!(
+            f,
+            "Time limit: {}",
+            self.time_limit_ms
+                .map(|ms| format!("{} ms", ms))
+                .unwrap_or_else(none)
+        )?;
+        writeln!(
+            f,
+            "Memory limit: {}",
+            self.memory_limit_mb
+                .map(|mb| format!("{} MB", mb))
+                .unwrap_or_else(none)
+        )?;
+
+        if !self.failed_tests.is_empty() {
+            writeln!(f, "Reference fails tests: {}", self.failed_tests.join(", "))?;
+        }
+
+        for warning in &self.warnings {
+            writeln!(f, "warning: {}", warning)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Importer {}
+
+impl Importer {
+    pub fn import(package_path: &Path, task: &str) -> Result<ImportReport, RunnerError> {
+        if task.is_empty() || task.contains('/') || task.contains("..") {
+            return Err(RunnerError::Other(format!("invalid task name '{}'", task)));
+        }
+
+        let scratch = ScratchDirectory::create()?;
+        let root = if package_path
+            .extension()
+            .map(|e| e == "zip")
+            .unwrap_or(false)
+        {
+            Importer::extract(package_path, scratch.path())?
+        } else {
+            package_path.to_path_buf()
+        };
+
+        let package = Package::read(&root)?;
+        info!(
+            "Importing {} package {:?} as '{}'",
+            package.format, root, task
+        );
+
+        let mut report = ImportReport {
+            format: package.format,
+            tests: package.tests.len(),
+            tests_without_answers: package.tests.iter().filter(|t| t.answer.is_none()).count(),
+            reference: None,
+            checker: None,
+            time_limit_ms: package.time_limit_ms,
+            memory_limit_mb: package.memory_limit_mb,
+            failed_tests: Vec::new(),
+            warnings: package.warnings.clone(),
+        };
+
+        let mut config = TaskConfig::try_load(task)?;
+        Importer::copy_tests(&package, task)?;
+
+        config.time_limit_ms = package.time_limit_ms.or(config.time_limit_ms);
+        config.memory_limit_mb = package.memory_limit_mb.or(config.memory_limit_mb);
+        // Kattis samples are shown in the problem statement anyway.
+        if package.format == PackageFormat::Kattis && config.public_tests.is_empty() {
+            config.public_tests = vec!["sample-*".to_string()];
+        }
+
+        match &package.reference {
+            Some(source) => {
+                Importer::install(&package, source, task)?;
+                config.reference = None;
+                report.reference = Some(format!("bin/{}", task));
+            }
+            None => report
+                .warnings
+                .push("no reference solution, tests without answers are skipped".to_string()),
+        }
+
+        if let Some(source) = &package.checker {
+            let name = format!("{}.checker", task);
+            Importer::install(&package, source, &name)?;
+            config.checker = Some(name.clone());
+            config.checker_interface = package.checker_interface;
+            report.checker = Some(format!("bin/{} ({:?})", name, package.checker_interface));
+        }
+
+        config.save(task)?;
+
+        if package.reference.is_some() {
+            report.failed_tests = Importer::validate(task, &config)?;
+        }
+
+        Ok(report)
+    }
+
+    fn extract(archive: &Path, destination: &Path) -> Result<PathBuf, RunnerError> {
+        let archive = archive.canonicalize().map_err(|_| RunnerError::NotFound)?;
+        let mut command = Command::new("unzip");
+        command.arg("-q").arg(&archive).arg("-d").arg(destination);
+
+        let process = BinaryRunner::execute_command(&mut command, "", &Environment::new())?;
+
+        if process.status_code != 0 {
+            return Err(RunnerError::Other(format!(
+                "cannot extract {:?}: {}",
+                archive, process.stderr
+            )));
+        }
+
+        // Archives often wrap the package in a single directory.
+        let entries: Vec<PathBuf> = fs::read_dir(destination)
+            .map_err(|e| RunnerError::Other(e.to_string()))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+
+        match entries.as_slice() {
+            [single] if single.is_dir() => Ok(single.clone()),
+            _ => Ok(destination.to_path_buf()),
+        }
+    }
+
+    // The tests are copied next to the old ones and swapped in at the end, so
+    // tests of an earlier import never mix with the new package.
+    fn copy_tests(package: &Package, task: &str) -> Result<(), RunnerError> {
+        let tests = task_directory(task).join("tests");
+        let directory = task_directory(task).join("tests.new");
+        let error = |e: std::io::Error| RunnerError::Other(e.to_string());
+
+        if directory.exists() {
+            fs::remove_dir_all(&directory).map_err(error)?;
+        }
+        fs::create_dir_all(&directory).map_err(error)?;
+
+        for test in &package.tests {
+            if test.answer.is_none() && package.reference.is_none() {
+                continue;
+            }
+
+            fs::copy(&test.input, directory.join(format!("{}.in", test.name))).map_err(error)?;
+
+            if let Some(answer) = &test.answer {
+                fs::copy(answer, directory.join(format!("{}.out", test.name))).map_err(error)?;
+            }
+        }
+
+        if tests.exists() {
+            fs::remove_dir_all(&tests).map_err(error)?;
+        }
+        fs::rename(&directory, &tests).map_err(error)
+    }
+
+    fn install(package: &Package, source_path: &Path, name: &str) -> Result<(), RunnerError> {
+        let language = Language::from_path(source_path).ok_or_else(|| {
+            RunnerError::Other(format!("{:?} is not a C or C++ source", source_path))
+        })?;
+        let source = fs::read_to_string(source_path)
+            .map_err(|_| RunnerError::Other(format!("cannot read {:?}", source_path)))?;
+
+        let includes: Vec<String> = package
+            .include_directories
+            .iter()
+            .map(|directory| format!("-I{}", directory.display()))
+            .collect();
+        let mut flags = language.flags().to_vec();
+        flags.extend(includes.iter().map(String::as_str));
+
+        let binary = Compiler::compile(&source, language, &flags)?;
+
+        fs::create_dir_all("bin")
+            .and_then(|_| fs::copy(&binary, Path::new("bin").join(name)))
+            .map_err(|e| RunnerError::Other(e.to_string()))?;
+
+        info!("Installed {:?} as bin/{}", source_path, name);
+        Ok(())
+    }
+
+    fn validate(task: &str, config: &TaskConfig) -> Result<Vec<String>, RunnerError> {
+        let suite = TestSuite::load(task, config)?;
+        let reference = BinaryRunner::binary_path(task).map_err(|_| RunnerError::NotFound)?;
+        let grade = Grader::grade_reference(task, &reference, &suite, config);
+
+        Ok(grade
+            .tests
+            .into_iter()
+            .filter(|test| test.verdict != Verdict::Accepted)
+            .map(|test| format!("{} ({})", test.test, test.verdict))
+            .collect())
+    }
+}
@@ -0,0 +1,26 @@
+pub mod assignment;
+pub mod calibration;
+pub mod checker;
+pub mod grader;
+pub mod importer;
+pub mod package;
+pub mod scoring;
+pub mod task_config;
+pub mod test_suite;
+pub mod verdict;
+
+use std::path::PathBuf;
+
+pub fn task_directory(task: &str) -> PathBuf {
+    PathBuf::from("tasks").join(task)
+}
+
+// Test name patterns of task configurations, a trailing `*` matches a prefix.
+pub fn matches_any(patterns: &[String], test: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => test.starts_with(prefix),
+            None => test == pattern,
+        })
+}
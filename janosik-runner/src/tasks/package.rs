@@ -0,0 +1,324 @@
+use crate::runners::compiler::Language;
+use crate::runners::runner_error::RunnerError;
+use crate::tasks::checker::CheckerInterface;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PackageFormat {
+    Tests,
+    Kattis,
+    Polygon,
+}
+
+impl fmt::Display for PackageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PackageFormat::Tests => "tests directory",
+            PackageFormat::Kattis => "Kattis",
+            PackageFormat::Polygon => "Polygon",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PackageTest {
+    pub name: String,
+    pub input: PathBuf,
+    pub answer: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct Package {
+    pub format: PackageFormat,
+    pub tests: Vec<PackageTest>,
+    pub reference: Option<PathBuf>,
+    pub checker: Option<PathBuf>,
+    pub checker_interface: CheckerInterface,
+    pub include_directories: Vec<PathBuf>,
+    pub time_limit_ms: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+    pub warnings: Vec<String>,
+}
+
+impl Package {
+    pub fn read(root: &Path) -> Result<Package, RunnerError> {
+        if !root.is_dir() {
+            return Err(RunnerError::Other(format!("{:?} is not a directory", root)));
+        }
+
+        if root.join("problem.xml").is_file() {
+            Package::read_polygon(root)
+        } else if root.join("problem.yaml").is_file() || root.join("data").is_dir() {
+            Ok(Package::read_kattis(root))
+        } else {
+            Ok(Package::read_tests(root))
+        }
+    }
+
+    fn new(format: PackageFormat) -> Package {
+        Package {
+            format,
+            tests: Vec::new(),
+            reference: None,
+            checker: None,
+            checker_interface: CheckerInterface::Testlib,
+            include_directories: Vec::new(),
+            time_limit_ms: None,
+            memory_limit_mb: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    // `<name>.in` with `<name>.out` or `<name>.ans`, in the root or in `tests`.
+    fn read_tests(root: &Path) -> Package {
+        let mut package = Package::new(PackageFormat::Tests);
+        let directory = if root.join("tests").is_dir() {
+            root.join("tests")
+        } else {
+            root.to_path_buf()
+        };
+
+        package.tests = paired_tests(&directory, "", &["out", "ans"]);
+
+        let sources: Vec<PathBuf> = files(root)
+            .into_iter()
+            .filter(|path| Language::from_path(path).is_some())
+            .collect();
+        let (checkers, solutions): (Vec<PathBuf>, Vec<PathBuf>) =
+            sources.into_iter().partition(|path| {
+                let stem = path.file_stem().unwrap_or_default();
+                stem == "checker" || stem == "check"
+            });
+
+        package.checker = checkers.into_iter().next();
+        package.reference = solutions.first().cloned();
+        if solutions.len() > 1 {
+            package
+                .warnings
+                .push(format!("several solutions, using {:?}", solutions[0]));
+        }
+
+        package.include_directories.push(root.to_path_buf());
+        package
+    }
+
+    fn read_kattis(root: &Path) -> Package {
+        let mut package = Package::new(PackageFormat::Kattis);
+        let data = root.join("data");
+
+        for group in &["sample", "secret"] {
+            let directory = data.join(group);
+            package
+                .tests
+                .extend(paired_tests(&directory, &format!("{}-", group), &["ans"]));
+
+            for subgroup in directories(&directory) {
+                let prefix = format!("{}-{}-", group, file_name(&subgroup));
+                package
+                    .tests
+                    .extend(paired_tests(&subgroup, &prefix, &["ans"]));
+            }
+        }
+
+        let yaml = fs::read_to_string(root.join("problem.yaml")).unwrap_or_default();
+        package.time_limit_ms = fs::read_to_string(root.join(".timelimit"))
+            .ok()
+            .or_else(|| yaml_value(&yaml, "time_limit"))
+            .and_then(|seconds| seconds.trim().parse::<f64>().ok())
+            .map(|seconds| (seconds * 1000.0) as u64);
+        package.memory_limit_mb = yaml_value(&yaml, "memory").and_then(|mb| mb.parse().ok());
+
+        let accepted: Vec<PathBuf> = files(&root.join("submissions").join("accepted"))
+            .into_iter()
+            .filter(|path| Language::from_path(path).is_some())
+            .collect();
+        package.reference = accepted.first().cloned();
+
+        let validators = root.join("output_validators");
+        let mut checkers: Vec<PathBuf> = files(&validators);
+        for directory in directories(&validators) {
+            checkers.extend(files(&directory));
+        }
+        checkers.retain(|path| Language::from_path(path).is_some());
+
+        if checkers.len() > 1 {
+            package.warnings.push(format!(
+                "output validator has {} source files, only single-file validators are supported",
+                checkers.len()
+            ));
+        } else if let Some(checker) = checkers.pop() {
+            package
+                .include_directories
+                .push(checker.parent().unwrap_or(root).to_path_buf());
+            package.checker = Some(checker);
+            package.checker_interface = CheckerInterface::Kattis;
+        }
+
+        package
+    }
+
+    fn read_polygon(root: &Path) -> Result<Package, RunnerError> {
+        let mut package = Package::new(PackageFormat::Polygon);
+        let xml = fs::read_to_string(root.join("problem.xml"))
+            .map_err(|_| RunnerError::Other("cannot read problem.xml".to_string()))?;
+
+        package.time_limit_ms = xml_tag(&xml, "time-limit").and_then(|ms| ms.parse().ok());
+        package.memory_limit_mb = xml_tag(&xml, "memory-limit")
+            .and_then(|bytes| bytes.parse::<u64>().ok())
+            .map(|bytes| bytes / (1024 * 1024));
+
+        let tests = root.join("tests");
+        for input in files(&tests) {
+            if input.extension().is_some() {
+                continue;
+            }
+
+            let answer = PathBuf::from(format!("{}.a", input.display()));
+            package.tests.push(PackageTest {
+                name: file_name(&input),
+                input,
+                answer: if answer.is_file() { Some(answer) } else { None },
+            });
+        }
+
+        if package.tests.is_empty() {
+            package.warnings.push(
+                "no tests in the package, download the full package with generated tests"
+                    .to_string(),
+            );
+        }
+
+        package.reference = xml_path_after(&xml, "tag=\"main\"").map(|path| root.join(path));
+        package.checker = xml_path_after(&xml, "<checker").map(|path| root.join(path));
+        package.include_directories = vec![root.to_path_buf(), root.join("files")];
+
+        Ok(package)
+    }
+}
+
+fn paired_tests(directory: &Path, prefix: &str, answer_extensions: &[&str]) -> Vec<PackageTest> {
+    files(directory)
+        .into_iter()
+        .filter(|path| path.extension().map(|e| e == "in").unwrap_or(false))
+        .map(|input| {
+            let answer = answer_extensions
+                .iter()
+                .map(|extension| input.with_extension(extension))
+                .find(|answer| answer.is_file());
+
+            PackageTest {
+                name: format!("{}{}", prefix, file_stem(&input)),
+                input,
+                answer,
+            }
+        })
+        .collect()
+}
+
+fn files(directory: &Path) -> Vec<PathBuf> {
+    entries(directory, |path| path.is_file())
+}
+
+fn directories(directory: &Path) -> Vec<PathBuf> {
+    entries(directory, |path| path.is_dir())
+}
+
+fn entries(directory: &Path, filter: fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = match fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| filter(path))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    entries.sort();
+    entries
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+// Enough of YAML for `key: value` lines of problem.yaml.
+fn yaml_value(yaml: &str, key: &str) -> Option<String> {
+    yaml.lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix(key)?.trim_start().strip_prefix(':'))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = start + xml[start..].find('<')?;
+
+    Some(xml[start..end].trim().to_string())
+}
+
+fn xml_path_after(xml: &str, marker: &str) -> Option<String> {
+    let start = xml.find(marker)?;
+    let path = start + xml[start..].find("path=\"")? + "path=\"".len();
+    let end = path + xml[path..].find('"')?;
+
+    Some(xml[path..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tasks::package::{xml_path_after, xml_tag, yaml_value};
+
+    #[test]
+    fn should_read_polygon_metadata() {
+        let xml = r#"<problem>
+            <judging><testset name="tests">
+                <time-limit>2000</time-limit>
+                <memory-limit>268435456</memory-limit>
+            </testset></judging>
+            <assets>
+                <checker name="std::wcmp.cpp" type="testlib">
+                    <source path="files/check.cpp" type="cpp.g++17"/>
+                </checker>
+                <solutions>
+                    <solution tag="rejected"><source path="solutions/slow.cpp"/></solution>
+                    <solution tag="main"><source path="solutions/main.cpp"/></solution>
+                </solutions>
+            </assets>
+        </problem>"#;
+
+        assert_eq!(xml_tag(xml, "time-limit"), Some("2000".to_string()));
+        assert_eq!(xml_tag(xml, "memory-limit"), Some("268435456".to_string()));
+        assert_eq!(
+            xml_path_after(xml, "tag=\"main\""),
+            Some("solutions/main.cpp".to_string())
+        );
+        assert_eq!(
+            xml_path_after(xml, "<checker"),
+            Some("files/check.cpp".to_string())
+        );
+    }
+
+    #[test]
+    fn should_read_kattis_limits() {
+        let yaml = "name: Sum\nlimits:\n  time_limit: 1.5\n  memory: 512\n";
+
+        assert_eq!(yaml_value(yaml, "time_limit"), Some("1.5".to_string()));
+        assert_eq!(yaml_value(yaml, "memory"), Some("512".to_string()));
+        assert_eq!(yaml_value(yaml, "validation"), None);
+    }
+}
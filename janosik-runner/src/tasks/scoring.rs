@@ -0,0 +1,125 @@
+use crate::tasks::grader::TestResult;
+use crate::tasks::matches_any;
+use crate::tasks::verdict::Verdict;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_POINTS: f64 = 100.0;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scoring {
+    // Points only when every test of the subtask passes.
+    #[default]
+    All,
+    // A share of the points for every passed test.
+    Proportional,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Subtask {
+    pub name: String,
+    pub points: f64,
+    // Test names, a trailing `*` matches a prefix.
+    pub tests: Vec<String>,
+    #[serde(default)]
+    pub scoring: Scoring,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SubtaskScore {
+    pub name: String,
+    pub points: f64,
+    pub max_points: f64,
+    pub passed: usize,
+    pub tests: usize,
+}
+
+impl Subtask {
+    // Tasks without subtasks are scored by the percentage of passed tests.
+    pub fn whole_suite() -> Subtask {
+        Subtask {
+            name: "all".to_string(),
+            points: DEFAULT_POINTS,
+            tests: vec!["*".to_string()],
+            scoring: Scoring::Proportional,
+        }
+    }
+
+    pub fn score(&self, results: &[TestResult]) -> SubtaskScore {
+        let verdicts: Vec<Verdict> = results
+            .iter()
+            .filter(|result| matches_any(&self.tests, &result.test))
+            .map(|result| result.verdict)
+            .collect();
+        let passed = verdicts.iter().filter(|&&v| v == Verdict::Accepted).count();
+
+        let points = match self.scoring {
+            _ if verdicts.is_empty() => 0.0,
+            Scoring::All if passed == verdicts.len() => self.points,
+            Scoring::All => 0.0,
+            Scoring::Proportional => self.points * passed as f64 / verdicts.len() as f64,
+        };
+
+        SubtaskScore {
+            name: self.name.clone(),
+            points,
+            max_points: self.points,
+            passed,
+            tests: verdicts.len(),
+        }
+    }
+}
+
+pub fn format_points(points: f64) -> String {
+    format!("{}", (points * 100.0).round() / 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tasks::grader::TestResult;
+    use crate::tasks::scoring::{format_points, Scoring, Subtask};
+    use crate::tasks::verdict::Verdict;
+
+    fn result(test: &str, verdict: Verdict) -> TestResult {
+        TestResult {
+            test: test.to_string(),
+            public: false,
+            verdict,
+            time_ms: 0,
+            memory_kb: 0,
+            output: String::new(),
+        }
+    }
+
+    fn subtask(scoring: Scoring) -> Subtask {
+        Subtask {
+            name: "small".to_string(),
+            points: 30.0,
+            tests: vec!["small-*".to_string(), "extra".to_string()],
+            scoring,
+        }
+    }
+
+    #[test]
+    fn should_score_subtasks() {
+        let results = vec![
+            result("small-1", Verdict::Accepted),
+            result("small-2", Verdict::WrongAnswer),
+            result("extra", Verdict::Accepted),
+            result("big-1", Verdict::TimeLimitExceeded),
+        ];
+
+        let all = subtask(Scoring::All).score(&results);
+        let proportional = subtask(Scoring::Proportional).score(&results);
+
+        assert_eq!((all.points, all.passed, all.tests), (0.0, 2, 3));
+        assert_eq!(proportional.points, 20.0);
+        assert_eq!(Subtask::whole_suite().score(&results).points, 50.0);
+    }
+
+    #[test]
+    fn should_round_points() {
+        assert_eq!(format_points(60.0), "60");
+        assert_eq!(format_points(100.0 / 3.0), "33.33");
+    }
+}
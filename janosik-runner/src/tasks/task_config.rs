@@ -0,0 +1,224 @@
+use crate::runners::binary_runner::{DEFAULT_TIME_LIMIT, MAX_TIME_LIMIT};
+use crate::runners::resource_control::{ResourceLimits, DEFAULT_PIDS_LIMIT};
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::Environment;
+use crate::runners::syscall_filter::SyscallPolicy;
+use crate::tasks::checker::CheckerInterface;
+use crate::tasks::scoring::Subtask;
+use crate::tasks::{matches_any, task_directory};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+use toml_edit::{value, DocumentMut};
+use tracing::{info, warn};
+
+const CONFIG_FILENAME: &str = "task.toml";
+
+// Tables have to come last, TOML does not allow plain values after them.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TaskConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checker: Option<String>,
+    pub checker_interface: CheckerInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_limit_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit_mb: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pids_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_syscalls: Option<SyscallPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_syscalls: Option<SyscallPolicy>,
+    pub input_files: Vec<String>,
+    pub output_files: Vec<String>,
+    pub public_tests: Vec<String>,
+    pub environment: Environment,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subtasks: Vec<Subtask>,
+}
+
+impl TaskConfig {
+    pub fn load(task: &str) -> TaskConfig {
+        TaskConfig::try_load(task).unwrap_or_else(|e| {
+            warn!("{:?}, using defaults", e);
+            TaskConfig::default()
+        })
+    }
+
+    // For changing the configuration, which must not start over from the
+    // defaults because of a typo.
+    pub fn try_load(task: &str) -> Result<TaskConfig, RunnerError> {
+        let path = task_directory(task).join(CONFIG_FILENAME);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => TaskConfig::parse(&path, &content),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                info!("No configuration for task '{}', using defaults", task);
+                Ok(TaskConfig::default())
+            }
+            Err(e) => Err(RunnerError::InvalidConfig(format!("{:?}: {}", path, e))),
+        }
+    }
+
+    fn parse(path: &Path, content: &str) -> Result<TaskConfig, RunnerError> {
+        toml::from_str(content)
+            .map_err(|e| RunnerError::InvalidConfig(format!("{:?}: {}", path, e)))
+    }
+
+    pub fn save(&self, task: &str) -> Result<(), RunnerError> {
+        let directory = task_directory(task);
+        let content = toml::to_string(self).map_err(|e| RunnerError::Other(e.to_string()))?;
+
+        fs::create_dir_all(&directory)
+            .and_then(|_| fs::write(directory.join(CONFIG_FILENAME), content))
+            .map_err(|e| RunnerError::Other(e.to_string()))
+    }
+
+    // Changes only `time_limit_ms` in the file, comments and all the other
+    // settings stay as they are.
+    pub fn save_time_limit(task: &str, time_limit: Duration) -> Result<(), RunnerError> {
+        let directory = task_directory(task);
+
+        fs::create_dir_all(&directory).map_err(|e| RunnerError::Other(e.to_string()))?;
+        TaskConfig::write_time_limit(&directory.join(CONFIG_FILENAME), time_limit)
+    }
+
+    fn write_time_limit(path: &Path, time_limit: Duration) -> Result<(), RunnerError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(RunnerError::Other(e.to_string())),
+        };
+
+        TaskConfig::parse(path, &content)?;
+        let mut document: DocumentMut = content
+            .parse()
+            .map_err(|e| RunnerError::InvalidConfig(format!("{:?}: {}", path, e)))?;
+        document["time_limit_ms"] = value(time_limit.as_millis() as i64);
+
+        fs::write(path, document.to_string()).map_err(|e| RunnerError::Other(e.to_string()))
+    }
+
+    pub fn time_limit(&self) -> Duration {
+        self.time_limit_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_TIME_LIMIT)
+            .min(MAX_TIME_LIMIT)
+    }
+
+    pub fn resource_limits(&self) -> ResourceLimits {
+        ResourceLimits {
+            memory_bytes: self.memory_limit_mb.map(|mb| mb * 1024 * 1024),
+            pids: self.pids_limit.or(Some(DEFAULT_PIDS_LIMIT)),
+            cpu_cores: self.cpu_limit,
+        }
+    }
+
+    pub fn submission_syscalls(&self) -> SyscallPolicy {
+        self.submission_syscalls.unwrap_or(SyscallPolicy::Strict)
+    }
+
+    pub fn reference_syscalls(&self) -> SyscallPolicy {
+        self.reference_syscalls.unwrap_or(SyscallPolicy::Relaxed)
+    }
+
+    // Tests are hidden unless listed.
+    pub fn is_public(&self, test: &str) -> bool {
+        matches_any(&self.public_tests, test)
+    }
+
+    pub fn subtasks(&self) -> Vec<Subtask> {
+        if self.subtasks.is_empty() {
+            vec![Subtask::whole_suite()]
+        } else {
+            self.subtasks.clone()
+        }
+    }
+
+    pub fn reference_binary(&self, task: &str) -> String {
+        self.reference.clone().unwrap_or_else(|| task.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runners::runner_error::RunnerError;
+    use crate::runners::scratch_directory::ScratchDirectory;
+    use crate::tasks::checker::CheckerInterface;
+    use crate::tasks::scoring::{Scoring, Subtask};
+    use crate::tasks::task_config::TaskConfig;
+    use std::fs;
+    use std::time::Duration;
+
+    #[test]
+    fn should_round_trip_configuration() {
+        let mut config = TaskConfig {
+            checker: Some("sum.checker".to_string()),
+            checker_interface: CheckerInterface::Kattis,
+            time_limit_ms: Some(1000),
+            input_files: vec!["input.txt".to_string()],
+            public_tests: vec!["sample-*".to_string()],
+            subtasks: vec![Subtask {
+                name: "small".to_string(),
+                points: 40.0,
+                tests: vec!["small-*".to_string()],
+                scoring: Scoring::Proportional,
+            }],
+            ..TaskConfig::default()
+        };
+        config
+            .environment
+            .insert("LIMIT".to_string(), "1".to_string());
+
+        let content = toml::to_string(&config).unwrap();
+
+        assert_eq!(toml::from_str::<TaskConfig>(&content).unwrap(), config);
+    }
+
+    #[test]
+    fn should_only_change_time_limit() {
+        let scratch = ScratchDirectory::create().unwrap();
+        let content = "# Checked by hand\nchecker = \"sum.checker\"\ntime_limit_ms = 100\n\n[[subtasks]]\nname = \"small\"\npoints = 40.0\ntests = [\"small-*\"]\n";
+        let path = scratch.write("task.toml", content).unwrap();
+
+        TaskConfig::write_time_limit(&path, Duration::from_millis(750)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            content.replace("time_limit_ms = 100", "time_limit_ms = 750")
+        );
+    }
+
+    #[test]
+    fn should_leave_broken_configuration_unchanged() {
+        let scratch = ScratchDirectory::create().unwrap();
+        let content = "checker = \"sum.checker\"\ntime_limit_ms = 100\npublic_tests = [\"1\"\n";
+        let path = scratch.write("task.toml", content).unwrap();
+
+        let result = TaskConfig::write_time_limit(&path, Duration::from_millis(750));
+
+        assert!(matches!(result, Err(RunnerError::InvalidConfig(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn should_match_public_tests() {
+        let config = TaskConfig {
+            public_tests: vec!["1".to_string(), "sample-*".to_string()],
+            ..TaskConfig::default()
+        };
+
+        assert!(config.is_public("1"));
+        assert!(config.is_public("sample-01"));
+        assert!(!config.is_public("10"));
+        assert!(!config.is_public("secret-01"));
+    }
+}
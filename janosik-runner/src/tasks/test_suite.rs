@@ -0,0 +1,76 @@
+use crate::runners::binary_runner::BinaryRunner;
+use crate::runners::runner_error::RunnerError;
+use crate::tasks::scoring::Subtask;
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::{matches_any, task_directory};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+const TESTS_DIRECTORY: &str = "tests";
+
+#[derive(Debug, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub input: String,
+    pub expected: String,
+    pub public: bool,
+}
+
+#[derive(Debug)]
+pub struct TestSuite {
+    pub task: String,
+    pub tests: Vec<TestCase>,
+    pub subtasks: Vec<Subtask>,
+}
+
+impl TestSuite {
+    pub fn load(task: &str, config: &TaskConfig) -> Result<TestSuite, RunnerError> {
+        let directory = task_directory(task).join(TESTS_DIRECTORY);
+        let mut names: Vec<String> = fs::read_dir(&directory)
+            .map_err(|_| RunnerError::Other(format!("no tests in {:?}", directory)))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(OsStr::new("in")))
+            .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+            .collect();
+        names.sort();
+
+        let mut tests = Vec::new();
+
+        for name in names {
+            let input = read(&directory.join(format!("{}.in", name)))?;
+            let expected = match fs::read_to_string(directory.join(format!("{}.out", name))) {
+                Ok(expected) => expected,
+                Err(_) => BinaryRunner::run(&config.reference_binary(task), &input)?,
+            };
+
+            tests.push(TestCase {
+                public: config.is_public(&name),
+                name,
+                input,
+                expected,
+            });
+        }
+
+        info!("Loaded {} tests of task '{}'", tests.len(), task);
+
+        let subtasks = config.subtasks();
+        for test in &tests {
+            if !subtasks.iter().any(|s| matches_any(&s.tests, &test.name)) {
+                warn!("Test {} of task '{}' is in no subtask", test.name, task);
+            }
+        }
+
+        Ok(TestSuite {
+            task: task.to_string(),
+            tests,
+            subtasks,
+        })
+    }
+}
+
+fn read(path: &Path) -> Result<String, RunnerError> {
+    fs::read_to_string(path).map_err(|_| RunnerError::Other(format!("cannot read {:?}", path)))
+}
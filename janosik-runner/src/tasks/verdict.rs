@@ -0,0 +1,54 @@
+use crate::runners::runner_error::RunnerError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Verdict {
+    #[serde(rename = "OK")]
+    Accepted,
+    #[serde(rename = "WA")]
+    WrongAnswer,
+    #[serde(rename = "TLE")]
+    TimeLimitExceeded,
+    #[serde(rename = "MLE")]
+    MemoryLimitExceeded,
+    #[serde(rename = "RE")]
+    RuntimeError,
+    #[serde(rename = "RF")]
+    ForbiddenSyscall,
+    #[serde(rename = "CE")]
+    CompilationError,
+    #[serde(rename = "ERR")]
+    InternalError,
+}
+
+impl Verdict {
+    pub fn from_error(error: &RunnerError) -> Verdict {
+        match error {
+            RunnerError::NoOutput => Verdict::WrongAnswer,
+            RunnerError::Timeout => Verdict::TimeLimitExceeded,
+            RunnerError::MemoryLimitExceeded => Verdict::MemoryLimitExceeded,
+            RunnerError::Crash | RunnerError::MemoryError(_) => Verdict::RuntimeError,
+            RunnerError::ForbiddenSyscall => Verdict::ForbiddenSyscall,
+            RunnerError::CompilationError(_) => Verdict::CompilationError,
+            _ => Verdict::InternalError,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Verdict::Accepted => "OK",
+            Verdict::WrongAnswer => "WA",
+            Verdict::TimeLimitExceeded => "TLE",
+            Verdict::MemoryLimitExceeded => "MLE",
+            Verdict::RuntimeError => "RE",
+            Verdict::ForbiddenSyscall => "RF",
+            Verdict::CompilationError => "CE",
+            Verdict::InternalError => "ERR",
+        };
+
+        write!(f, "{}", code)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+    utils::content_safe,
+};
+
+use crate::commands::{make_settings, reply, run_job};
+use crate::runners::benchmark::{BenchmarkResult, Statistics, WARMUP_RUNS};
+use crate::runners::job::{Job, JobOutput};
+use crate::runners::runner_error::RunnerError;
+use std::time::Duration;
+use tracing::info;
+
+const DEFAULT_RUNS: usize = 10;
+const MAX_RUNS: usize = 50;
+
+#[group]
+#[commands(bench)]
+struct Benchmarks;
+
+#[command]
+pub async fn bench(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let content = content_safe(&ctx.cache, args.rest(), &make_settings(msg)).await;
+    let (program_name, runs, reference, input) = parse_bench_command(&content);
+
+    info!(
+        "Program: {}, runs: {}, reference: {:?}",
+        program_name, runs, reference
+    );
+
+    let mut results = vec![(
+        program_name.clone(),
+        run_benchmark(&program_name, &input, runs).await,
+    )];
+    if let Some(reference) = reference {
+        let result = run_benchmark(&reference, &input, runs).await;
+        results.push((reference, result));
+    }
+
+    reply(&ctx, msg, &format_results(runs, results)).await?;
+    Ok(())
+}
+
+async fn run_benchmark(
+    program: &str,
+    input: &str,
+    runs: usize,
+) -> Result<BenchmarkResult, RunnerError> {
+    let job = Job::Benchmark {
+        program: program.to_string(),
+        input: input.to_string(),
+        runs,
+    };
+
+    run_job(job).await.and_then(JobOutput::benchmark)
+}
+
+fn parse_bench_command(content: &str) -> (String, usize, Option<String>, String) {
+    let mut parts = content.split("```");
+    let mut words = parts.next().unwrap_or("").split_whitespace();
+    let input = parts.next().unwrap_or("").trim_matches('\n').to_string();
+
+    let program_name = words.next().unwrap_or("").to_string();
+    let mut runs = DEFAULT_RUNS;
+    let mut reference = None;
+
+    for word in words {
+        match word.parse::<usize>() {
+            Ok(n) => runs = n.max(1).min(MAX_RUNS),
+            Err(_) => reference = Some(word.to_string()),
+        }
+    }
+
+    (program_name, runs, reference, input)
+}
+
+fn format_results(
+    runs: usize,
+    results: Vec<(String, Result<BenchmarkResult, RunnerError>)>,
+) -> String {
+    let mut table = format!(
+        "{:<16} {:>10} {:>10} {:>10} {:>10}\n",
+        "", "min", "median", "mean", "stddev"
+    );
+    let mut errors = Vec::new();
+
+    for (program_name, result) in results {
+        match result {
+            Ok(result) => {
+                table.push_str(&format_row(&program_name, "wall", &result.wall_time));
+                table.push_str(&format_row(&program_name, "cpu", &result.cpu_time));
+            }
+            Err(e) => errors.push(runner_error_message(e, &program_name)),
+        }
+    }
+
+    let mut output = format!(
+        "{}\n```\n{}```",
+        bench_header_message(runs, WARMUP_RUNS),
+        table
+    );
+    for error in errors {
+        output.push_str(&format!("\n{}", error));
+    }
+
+    output
+}
+
+fn format_row(program_name: &str, kind: &str, statistics: &Statistics) -> String {
+    let name: String = program_name.chars().take(11).collect();
+
+    format!(
+        "{:<11} {:<4} {:>10} {:>10} {:>10} {:>10}\n",
+        name,
+        kind,
+        format_duration(statistics.min),
+        format_duration(statistics.median),
+        format_duration(statistics.mean),
+        format_duration(statistics.stddev)
+    )
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
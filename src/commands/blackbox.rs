@@ -9,47 +9,71 @@ use serenity::{
     utils::content_safe,
 };
 
-use crate::commands::{make_settings, send_message};
-use crate::runners::binary_runner::BinaryRunner;
-use crate::runners::runner_error::RunnerError;
-use serenity::utils::MessageBuilder;
+use crate::commands::diff::render_diff;
+use crate::commands::files::{input_files, send_files};
+use crate::commands::history::{format_run, run_and_record, run_program};
+use crate::commands::{make_settings, reply};
+use crate::runners::run_request::RunRequest;
+use crate::runners::scratch_directory::NamedFile;
 use tracing::{debug, info};
 
 #[group]
-#[commands(blackbox)]
+#[commands(blackbox, check)]
 struct Blackbox;
 
 #[command]
 pub async fn blackbox(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-    let (content, program_name, input) = parse_blackbox_command(ctx, msg, args).await;
+    let (output, attached) = blackbox_output(ctx, msg, args.rest()).await;
+
+    reply(&ctx, msg, &output).await?;
+    send_files(&ctx, msg, &attached).await;
+    Ok(())
+}
+
+#[command]
+pub async fn check(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let output = check_output(ctx, msg, args.rest()).await;
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+// The reply and the output files too large to be shown in it.
+pub async fn blackbox_output(ctx: &Context, msg: &Message, rest: &str) -> (String, Vec<NamedFile>) {
+    let (content, program_name, input) = parse_blackbox_command(ctx, msg, rest).await;
 
     debug!("Content: {}", content);
     info!("Program: {}", program_name);
     info!("Input: {}", input);
 
-    let output = match BinaryRunner::run(&program_name, &input) {
-        Ok(out) => out,
-        Err(e) => match e {
-            RunnerError::NoInput => no_input_message(),
-            RunnerError::Timeout => timeout_message(&program_name),
-            RunnerError::NotFound => not_found_message(&program_name),
-            RunnerError::NoOutput => no_output_message(&program_name),
-            RunnerError::Crash => crash_message(&program_name),
-            RunnerError::Other(e) => e,
-        },
-    };
+    let files = input_files(msg, &program_name, &input).await;
+    let (run, result) = run_and_record(msg.author.id, &program_name, &input, &files).await;
+    format_run(&run, result)
+}
 
-    let content = MessageBuilder::new().user(&msg.author).build();
-    send_message(&ctx, msg, &format!("{}\n{}", content, output)).await?;
-    Ok(())
+pub async fn check_output(ctx: &Context, msg: &Message, rest: &str) -> String {
+    let content = content_safe(&ctx.cache, rest, &make_settings(msg)).await;
+    let (program_name, blocks) = extract_program_name_and_blocks(&content);
+
+    info!("Program: {}", program_name);
+
+    match blocks.as_slice() {
+        [input, expected] => match run_program(&RunRequest::new(&program_name, input)).await {
+            Ok(execution) => {
+                render_diff(&execution.output, expected).unwrap_or_else(correct_answer_message)
+            }
+            Err(e) => runner_error_message(e, &program_name),
+        },
+        _ => no_answer_message(),
+    }
 }
 
-async fn parse_blackbox_command(
+pub async fn parse_blackbox_command(
     ctx: &Context,
     msg: &Message,
-    args: Args,
+    rest: &str,
 ) -> (String, String, String) {
-    let content = trim_content(ctx, msg, &args).await;
+    let content = trim_content(ctx, msg, rest).await;
     let (program_name, input) = extract_program_name_and_input(&content);
 
     (content, program_name, input)
@@ -60,12 +84,36 @@ fn extract_program_name_and_input(content: &str) -> (String, String) {
     (program_name.unwrap(), remove_ticks_or_empty(input))
 }
 
+fn extract_program_name_and_blocks(content: &str) -> (String, Vec<String>) {
+    let program_name = content.split_whitespace().next().unwrap_or("").to_string();
+
+    (program_name, extract_code_blocks(content))
+}
+
+pub fn extract_code_blocks(content: &str) -> Vec<String> {
+    content
+        .split("```")
+        .skip(1)
+        .step_by(2)
+        .map(|block| block.trim_matches('\n').to_string())
+        .collect()
+}
+
+pub fn split_language_tag(block: &str) -> (Option<&str>, &str) {
+    match block.find('\n') {
+        Some(end) if !block[..end].trim().is_empty() && !block[..end].contains(' ') => {
+            (Some(block[..end].trim()), &block[end + 1..])
+        }
+        _ => (None, block),
+    }
+}
+
 fn remove_ticks_or_empty(str: Option<String>) -> String {
     str.map_or(String::new(), |c| c.replace('`', ""))
 }
 
-async fn trim_content(ctx: &Context, msg: &Message, args: &Args) -> String {
-    content_safe(&ctx.cache, &args.rest(), &make_settings(msg))
+async fn trim_content(ctx: &Context, msg: &Message, rest: &str) -> String {
+    content_safe(&ctx.cache, rest, &make_settings(msg))
         .await
         .replace('\n', " ")
 }
@@ -0,0 +1,69 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::{reply, run_job, send_message};
+use crate::runners::job::{Job, JobOutput};
+use crate::tasks::calibration::{is_valid_multiplier, DEFAULT_FLOOR, DEFAULT_MULTIPLIER};
+use std::time::Duration;
+use tracing::info;
+
+#[group]
+#[commands(calibrate)]
+struct Calibration;
+
+#[command]
+#[required_permissions(ADMINISTRATOR)]
+#[delimiters(' ')]
+pub async fn calibrate(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let task = match args.single::<String>() {
+        Ok(task) => task,
+        Err(_) => {
+            send_message(&ctx, msg, &no_task_message()).await?;
+            return Ok(());
+        }
+    };
+    let multiplier = args.single::<f64>().unwrap_or(DEFAULT_MULTIPLIER);
+    let floor = args
+        .single::<u64>()
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_FLOOR);
+
+    if !is_valid_multiplier(multiplier) {
+        send_message(&ctx, msg, &invalid_multiplier_message()).await?;
+        return Ok(());
+    }
+
+    info!("Calibrating time limit of '{}' (x{})", task, multiplier);
+
+    let job = Job::Calibrate {
+        task: task.clone(),
+        multiplier,
+        floor,
+    };
+
+    // Measured where submissions run, but saved next to the bot.
+    let output = match run_job(job)
+        .await
+        .and_then(JobOutput::calibration)
+        .and_then(|calibration| calibration.save(&task).map(|_| calibration))
+    {
+        Ok(calibration) => calibration_message(
+            &task,
+            calibration.tests,
+            &calibration.worst_test,
+            calibration.worst_time.as_millis(),
+            calibration.time_limit.as_millis(),
+        ),
+        Err(e) => runner_error_message(e, &task),
+    };
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::{reply, run_job};
+use crate::runners::complexity::{ComplexityEstimator, Fit, Measurement};
+use crate::runners::job::{Job, JobOutput};
+use tracing::info;
+
+const CHART_HEIGHT: usize = 8;
+const COLUMN_WIDTH: usize = 3;
+
+#[group]
+#[commands(complexity)]
+struct Complexity;
+
+#[command]
+#[delimiters(' ')]
+pub async fn complexity(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let program_name = match args.single::<String>() {
+        Ok(program_name) => program_name,
+        Err(_) => {
+            reply(&ctx, msg, &no_task_message()).await?;
+            return Ok(());
+        }
+    };
+    let task = args
+        .single::<String>()
+        .unwrap_or_else(|_| program_name.clone());
+
+    info!("Program: {}, task: {}", program_name, task);
+
+    let job = Job::Complexity {
+        program: program_name.clone(),
+        task,
+    };
+
+    let output = match run_job(job).await.and_then(JobOutput::measurements) {
+        Ok(measurements) => match ComplexityEstimator::best_fit(&measurements) {
+            Some(fit) => format!(
+                "{}\n```\n{}```",
+                complexity_message(&fit.complexity.to_string()),
+                chart(&measurements, &fit)
+            ),
+            None => too_few_measurements_message(),
+        },
+        Err(e) => runner_error_message(e, &program_name),
+    };
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+fn chart(measurements: &[Measurement], fit: &Fit) -> String {
+    let measured: Vec<f64> = measurements.iter().map(|m| m.time.as_secs_f64()).collect();
+    let fitted: Vec<f64> = measurements
+        .iter()
+        .map(|m| fit.predict(m.size as f64))
+        .collect();
+    let max = measured
+        .iter()
+        .chain(fitted.iter())
+        .cloned()
+        .fold(f64::EPSILON, f64::max);
+
+    let row_of = |t: f64| ((t / max) * (CHART_HEIGHT - 1) as f64).round() as usize;
+    let mut rows = vec![vec![' '; measurements.len() * COLUMN_WIDTH]; CHART_HEIGHT];
+
+    for (i, (&m, &f)) in measured.iter().zip(fitted.iter()).enumerate() {
+        let column = i * COLUMN_WIDTH + 1;
+        rows[CHART_HEIGHT - 1 - row_of(f.max(0.0))][column] = '·';
+        rows[CHART_HEIGHT - 1 - row_of(m)][column] = '*';
+    }
+
+    let mut chart = format!("{:.0}ms\n", max * 1000.0);
+    for row in rows {
+        chart.push_str(&format!("|{}\n", row.into_iter().collect::<String>()));
+    }
+    chart.push_str(&format!(
+        "+{}\n n: {} .. {}\n",
+        "-".repeat(measurements.len() * COLUMN_WIDTH),
+        measurements.first().map_or(0, |m| m.size),
+        measurements.last().map_or(0, |m| m.size)
+    ));
+
+    chart
+}
@@ -0,0 +1,86 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::{is_administrator, send_message};
+use crate::runners::runner_error::RunnerError;
+use crate::tasks::assignment::{Assignments, Deadline};
+use chrono::{DateTime, FixedOffset, Local, Utc};
+
+const TIME_FORMAT: &str = "%d.%m.%Y %H:%M";
+
+#[group]
+#[commands(deadlines)]
+struct Deadlines;
+
+#[command]
+pub async fn deadlines(ctx: &Context, msg: &Message) -> CommandResult {
+    let administrator = is_administrator(ctx, msg).await;
+    let assignments = match load_assignments(administrator) {
+        Ok(assignments) => assignments,
+        Err(message) => {
+            send_message(&ctx, msg, &message).await?;
+            return Ok(());
+        }
+    };
+    let upcoming = assignments.upcoming(Utc::now());
+
+    let output = if upcoming.is_empty() {
+        no_deadlines_message()
+    } else {
+        let mut output = deadlines_header_message();
+
+        for assignment in upcoming {
+            output.push('\n');
+            output.push_str(&deadline_entry_message(
+                &assignment.name,
+                &assignment.tasks.join("`, `"),
+                &format_time(assignment.closes),
+            ));
+        }
+
+        output
+    };
+
+    send_message(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+// Nothing is submitted or judged while the assignments cannot be read.
+// Administrators are shown what is wrong with the file.
+pub fn load_assignments(administrator: bool) -> Result<Assignments, String> {
+    Assignments::load().map_err(|e| match e {
+        RunnerError::InvalidConfig(explanation) if administrator => {
+            invalid_config_message(&explanation)
+        }
+        _ => invalid_assignments_message(),
+    })
+}
+
+// Administrators are never locked out of a task.
+pub fn rejection_message(
+    assignments: &Assignments,
+    task: &str,
+    administrator: bool,
+) -> Option<String> {
+    if administrator {
+        return None;
+    }
+
+    match assignments.deadline(task, Utc::now()) {
+        Deadline::NotOpen(opens) => Some(deadline_not_open_message(task, &format_time(opens))),
+        Deadline::Closed(closes) => Some(deadline_closed_message(task, &format_time(closes))),
+        _ => None,
+    }
+}
+
+// Deadlines are shown in the time zone of the server.
+fn format_time(time: DateTime<FixedOffset>) -> String {
+    time.with_timezone(&Local).format(TIME_FORMAT).to_string()
+}
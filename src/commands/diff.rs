@@ -0,0 +1,245 @@
+use crate::commands::locale::{diff_summary_message, mismatch_message};
+use std::cmp::max;
+
+const SHORT_OUTPUT_LINES: usize = 15;
+const CONTEXT_LINES: usize = 2;
+const LINE_WIDTH: usize = 40;
+const COLUMN_WIDTH: usize = 28;
+
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+pub fn render_diff(expected: &str, actual: &str) -> Option<String> {
+    let expected = lines(expected);
+    let actual = lines(actual);
+    let mismatch = first_mismatch(&expected, &actual)?;
+
+    let details = if max(expected.len(), actual.len()) <= SHORT_OUTPUT_LINES {
+        unified(&expected, &actual)
+    } else {
+        side_by_side(&expected, &actual, &mismatch)
+    };
+
+    Some(format!(
+        "{}\n{}",
+        mismatch_message(mismatch.line, mismatch.column),
+        details
+    ))
+}
+
+fn lines(output: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = output.lines().map(|l| l.trim_end()).collect();
+
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    lines
+}
+
+fn first_mismatch(expected: &[&str], actual: &[&str]) -> Option<Mismatch> {
+    for i in 0..max(expected.len(), actual.len()) {
+        let e = expected.get(i).unwrap_or(&"");
+        let a = actual.get(i).unwrap_or(&"");
+
+        if i >= expected.len() || i >= actual.len() || e != a {
+            let column = e.chars().zip(a.chars()).take_while(|(e, a)| e == a).count();
+
+            return Some(Mismatch {
+                line: i + 1,
+                column: column + 1,
+            });
+        }
+    }
+
+    None
+}
+
+fn unified(expected: &[&str], actual: &[&str]) -> String {
+    let mut block = String::from("```diff\n");
+
+    for line in diff_lines(expected, actual) {
+        let (sign, content) = match line {
+            DiffLine::Same(l) => (' ', l),
+            DiffLine::Removed(l) => ('-', l),
+            DiffLine::Added(l) => ('+', l),
+        };
+
+        block.push_str(&format!("{}{}\n", sign, shorten(content, LINE_WIDTH)));
+    }
+
+    block.push_str("```");
+    block
+}
+
+fn diff_lines<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let (n, m) = (expected.len(), actual.len());
+    let mut common = vec![vec![0; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                max(common[i + 1][j], common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            diff.push(DiffLine::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || common[i + 1][j] >= common[i][j + 1]) {
+            diff.push(DiffLine::Removed(expected[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(actual[j]));
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+fn side_by_side(expected: &[&str], actual: &[&str], mismatch: &Mismatch) -> String {
+    let first = mismatch.line.saturating_sub(CONTEXT_LINES + 1);
+    let last = mismatch.line + CONTEXT_LINES;
+    let number_width = last.to_string().len();
+
+    let mut block = String::from("```\n");
+
+    for i in first..last {
+        let (e, a) = match (expected.get(i), actual.get(i)) {
+            (None, None) => break,
+            (e, a) => (e.unwrap_or(&""), a.unwrap_or(&"")),
+        };
+
+        let marker = if i >= expected.len() || i >= actual.len() || e != a {
+            '≠'
+        } else {
+            '|'
+        };
+
+        block.push_str(&format!(
+            "{:>nw$} {:<cw$} {} {}\n",
+            i + 1,
+            shorten(e, COLUMN_WIDTH),
+            marker,
+            shorten(a, COLUMN_WIDTH),
+            nw = number_width,
+            cw = COLUMN_WIDTH
+        ));
+    }
+
+    block.push_str("```");
+
+    let (same, different, missing, extra) = count_lines(expected, actual);
+    format!(
+        "{}\n{}",
+        block,
+        diff_summary_message(same, different, missing, extra)
+    )
+}
+
+fn count_lines(expected: &[&str], actual: &[&str]) -> (usize, usize, usize, usize) {
+    let compared = expected.len().min(actual.len());
+    let same = expected
+        .iter()
+        .zip(actual.iter())
+        .filter(|(e, a)| e == a)
+        .count();
+
+    (
+        same,
+        compared - same,
+        expected.len() - compared,
+        actual.len() - compared,
+    )
+}
+
+fn shorten(line: &str, width: usize) -> String {
+    if line.chars().count() <= width {
+        return line.to_string();
+    }
+
+    let mut short: String = line.chars().take(width - 1).collect();
+    short.push('…');
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::diff::{
+        diff_lines, first_mismatch, lines, render_diff, DiffLine, Mismatch,
+    };
+
+    #[test]
+    fn should_ignore_trailing_whitespace() {
+        assert_eq!(render_diff("1 2\n3\n", "1 2  \n3\n\n"), None);
+    }
+
+    #[test]
+    fn should_find_first_mismatching_column() {
+        let expected = lines("abc\ndef");
+        let actual = lines("abc\ndxf");
+
+        assert_eq!(
+            first_mismatch(&expected, &actual),
+            Some(Mismatch { line: 2, column: 2 })
+        );
+    }
+
+    #[test]
+    fn should_report_missing_lines() {
+        let expected = lines("1\n2\n3");
+        let actual = lines("1\n2");
+
+        assert_eq!(
+            first_mismatch(&expected, &actual),
+            Some(Mismatch { line: 3, column: 1 })
+        );
+    }
+
+    #[test]
+    fn should_diff_lines() {
+        let diff = diff_lines(&["a", "b", "c"], &["a", "x", "c"]);
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("x"),
+                DiffLine::Same("c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_summarize_long_outputs() {
+        let expected: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let mut actual = expected.clone();
+        actual[50] = "x".to_string();
+
+        let diff = render_diff(&expected.join("\n"), &actual.join("\n")).unwrap();
+
+        assert!(diff.contains("51 50"));
+        assert!(diff.contains("≠ x"));
+        assert!(!diff.contains("```diff"));
+    }
+}
@@ -0,0 +1,79 @@
+use serenity::model::channel::Message;
+use serenity::prelude::*;
+
+use crate::commands::blackbox::{blackbox_output, check_output};
+use crate::commands::files::send_files;
+use crate::commands::interact::interact_output;
+use crate::commands::reactions::add_controls;
+use crate::commands::sanitize::sanitize_output;
+use crate::commands::{mention_reply, shorten_reply, PREFIX};
+use crate::replies::reply_tracker::TrackedReply;
+use crate::runners::scratch_directory::NamedFile;
+use crate::REPLIES;
+use tracing::{error, info};
+
+const RERUNNABLE_COMMANDS: &[&str] = &["blackbox", "check", "interact", "sanitize"];
+
+pub async fn handle_edit(ctx: &Context, msg: &Message, reply: TrackedReply) {
+    info!("Re-running edited command by user '{}'", msg.author.name);
+
+    REPLIES.set_content(msg.id, &msg.content);
+    update_reply(ctx, msg, reply).await;
+}
+
+pub async fn update_reply(ctx: &Context, msg: &Message, reply: TrackedReply) {
+    let (output, attached) = match command_output(ctx, msg).await {
+        Some(output) => output,
+        None => return,
+    };
+
+    let content = mention_reply(msg, &output);
+    let short = shorten_reply(&content);
+    let truncated = short.is_some();
+    let shown = short.unwrap_or_else(|| content.clone());
+
+    match reply
+        .channel_id
+        .edit_message(&ctx.http, reply.reply_id, |m| m.content(shown))
+        .await
+    {
+        Ok(edited) => {
+            REPLIES.set_full_reply(msg.id, if truncated { Some(content) } else { None });
+            add_controls(ctx, msg, &edited, truncated).await;
+            // Edited messages cannot get attachments, so they follow the reply.
+            send_files(ctx, msg, &attached).await;
+        }
+        Err(e) => error!("Error editing reply: {:?}", e),
+    }
+}
+
+pub fn is_rerunnable(msg: &Message) -> bool {
+    match split_command(&msg.content) {
+        Some((name, _)) => RERUNNABLE_COMMANDS.contains(&name),
+        None => false,
+    }
+}
+
+async fn command_output(ctx: &Context, msg: &Message) -> Option<(String, Vec<NamedFile>)> {
+    let (name, rest) = split_command(&msg.content)?;
+
+    let output = match name {
+        "blackbox" => return Some(blackbox_output(ctx, msg, rest).await),
+        "check" => check_output(ctx, msg, rest).await,
+        "interact" => interact_output(ctx, msg, rest).await,
+        "sanitize" => sanitize_output(ctx, msg, rest).await,
+        _ => return None,
+    };
+
+    Some((output, Vec::new()))
+}
+
+fn split_command(content: &str) -> Option<(&str, &str)> {
+    let content = content.strip_prefix(PREFIX)?.trim_start();
+    let name_end = content
+        .find(char::is_whitespace)
+        .unwrap_or_else(|| content.len());
+    let (name, rest) = content.split_at(name_end);
+
+    Some((name, rest.trim_start()))
+}
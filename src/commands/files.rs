@@ -0,0 +1,101 @@
+use crate::commands::locale::*;
+use serenity::model::channel::Message;
+use serenity::prelude::*;
+
+use crate::runners::scratch_directory::NamedFile;
+use crate::tasks::task_config::TaskConfig;
+use tracing::{error, info};
+
+const INLINE_FILE_LIMIT: usize = 500;
+
+pub async fn input_files(msg: &Message, program_name: &str, input: &str) -> Vec<NamedFile> {
+    let config = TaskConfig::load(program_name);
+    let mut files = Vec::new();
+
+    for name in config.input_files {
+        let attachment = msg.attachments.iter().find(|a| a.filename == name);
+
+        let content = match attachment {
+            Some(attachment) => match attachment.download().await {
+                Ok(content) => content,
+                Err(e) => {
+                    error!("Error downloading {}: {:?}", name, e);
+                    continue;
+                }
+            },
+            None if !input.is_empty() => input.as_bytes().to_vec(),
+            None => continue,
+        };
+
+        info!("Input file {}: {} bytes", name, content.len());
+        files.push(NamedFile { name, content });
+    }
+
+    files
+}
+
+// Small text files are shown in the reply, the rest have to be uploaded.
+pub fn format_files(files: Vec<NamedFile>) -> (String, Vec<NamedFile>) {
+    let mut shown = String::new();
+    let mut attached = Vec::new();
+
+    for file in files {
+        match String::from_utf8(file.content.clone()) {
+            Ok(text) if text.chars().count() <= INLINE_FILE_LIMIT => {
+                shown.push_str(&format!(
+                    "{}\n```\n{}\n```\n",
+                    output_file_message(&file.name),
+                    text.trim_end()
+                ));
+            }
+            _ => {
+                shown.push_str(&format!("{}\n", attached_file_message(&file.name)));
+                attached.push(file);
+            }
+        }
+    }
+
+    (shown, attached)
+}
+
+pub async fn send_files(ctx: &Context, msg: &Message, files: &[NamedFile]) {
+    if files.is_empty() {
+        return;
+    }
+
+    let attachments = files
+        .iter()
+        .map(|f| (f.content.as_slice(), f.name.as_str()))
+        .collect::<Vec<_>>();
+
+    if let Err(e) = msg
+        .channel_id
+        .send_files(&ctx.http, attachments, |m| m)
+        .await
+    {
+        error!("Error sending output files: {:?}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::files::format_files;
+    use crate::runners::scratch_directory::NamedFile;
+
+    #[test]
+    fn should_attach_large_files() {
+        let small = NamedFile {
+            name: "small.txt".to_string(),
+            content: b"42\n".to_vec(),
+        };
+        let large = NamedFile {
+            name: "large.txt".to_string(),
+            content: vec![b'x'; 1000],
+        };
+
+        let (shown, attached) = format_files(vec![small, large.clone()]);
+
+        assert!(shown.contains("```\n42\n```"));
+        assert_eq!(attached, vec![large]);
+    }
+}
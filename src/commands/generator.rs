@@ -0,0 +1,68 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::history::run_program;
+use crate::commands::{code_block, reply, run_job, send_message};
+use crate::runners::generator::Generator;
+use crate::runners::job::{Job, JobOutput};
+use crate::runners::run_request::RunRequest;
+use tracing::info;
+
+const DEFAULT_SIZE: u32 = 10;
+const BLOCK_LIMIT: usize = 800;
+
+#[group]
+#[commands(gen)]
+struct Generators;
+
+#[command]
+#[delimiters(' ')]
+pub async fn gen(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let task = match args.single::<String>() {
+        Ok(task) => task,
+        Err(_) => {
+            send_message(&ctx, msg, &no_task_message()).await?;
+            return Ok(());
+        }
+    };
+    let size = args.single::<u32>().unwrap_or(DEFAULT_SIZE);
+    let seed = args.single::<u64>().unwrap_or_else(|_| rand::random());
+
+    info!("Task: {}, size: {}, seed: {}", task, size, seed);
+
+    let job = Job::Generate {
+        task: task.clone(),
+        size,
+        seed,
+    };
+
+    let output = match run_job(job).await.and_then(JobOutput::generated) {
+        Ok(input) => format_generated(&task, size, seed, &input).await,
+        Err(e) => runner_error_message(e, &Generator::name(&task)),
+    };
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+async fn format_generated(task: &str, size: u32, seed: u64, input: &str) -> String {
+    let output = match run_program(&RunRequest::new(task, input)).await {
+        Ok(execution) => code_block(&execution.output, BLOCK_LIMIT),
+        Err(e) => runner_error_message(e, task),
+    };
+
+    format!(
+        "{}\n{}\n{}\n{}",
+        generated_message(task, size, seed),
+        code_block(input, BLOCK_LIMIT),
+        reference_output_message(),
+        output
+    )
+}
@@ -0,0 +1,165 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::{channel::Message, id::UserId},
+};
+
+use crate::commands::diff::render_diff;
+use crate::commands::files::{format_files, input_files, send_files};
+use crate::commands::{reply, run_job, send_message};
+use crate::database::run_handler::{Run, RunHandler};
+use crate::runners::binary_runner::{BinaryRunner, Execution};
+use crate::runners::job::{Job, JobOutput};
+use crate::runners::run_request::{RunRequest, RunResult};
+use crate::runners::runner_error::RunnerError;
+use crate::runners::scratch_directory::NamedFile;
+use crate::tasks::task_config::TaskConfig;
+use crate::DATABASE;
+use tracing::{error, info};
+
+const HISTORY_LIMIT: u32 = 10;
+
+#[group]
+#[commands(history, rerun)]
+struct History;
+
+#[command]
+#[delimiters(' ')]
+pub async fn history(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let runs = DATABASE
+        .get_runs(*msg.author.id.as_u64(), args.current(), HISTORY_LIMIT)
+        .await;
+
+    send_message(&ctx, msg, &list_runs(&runs)).await?;
+    Ok(())
+}
+
+#[command]
+#[delimiters(' ')]
+pub async fn rerun(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let run_id = match args.single::<i64>() {
+        Ok(id) => id,
+        Err(_) => {
+            send_message(&ctx, msg, &invalid_run_id_message()).await?;
+            return Ok(());
+        }
+    };
+
+    let previous = match DATABASE.get_run(run_id).await {
+        Some(run) if run.user_id == *msg.author.id.as_u64() => run,
+        _ => {
+            send_message(&ctx, msg, &run_not_found_message(run_id)).await?;
+            return Ok(());
+        }
+    };
+
+    info!("Rerunning {} of task '{}'", run_id, previous.task_id);
+
+    let files = input_files(msg, &previous.task_id, &previous.input).await;
+    let (run, result) =
+        run_and_record(msg.author.id, &previous.task_id, &previous.input, &files).await;
+    let changed = run.binary_hash != previous.binary_hash;
+    let diff = match (&result, changed) {
+        (Ok(execution), true) => render_diff(&previous.output, &execution.output),
+        _ => None,
+    };
+    let (mut output, attached) = format_run(&run, result);
+
+    if changed {
+        output = format!("{}\n{}", binary_changed_message(), output);
+    }
+
+    if let Some(diff) = diff {
+        output = format!("{}\n{}", output, diff);
+    }
+
+    reply(&ctx, msg, &output).await?;
+    send_files(&ctx, msg, &attached).await;
+    Ok(())
+}
+
+pub async fn run_and_record(
+    user_id: UserId,
+    program_name: &str,
+    input: &str,
+    files: &[NamedFile],
+) -> (Run, Result<Execution, RunnerError>) {
+    let result =
+        run_program(&RunRequest::new(program_name, input).with_files(files.to_vec())).await;
+
+    let (verdict, output, time_ms) = match &result {
+        Ok(execution) => (
+            "OK",
+            execution.output.clone(),
+            execution.usage.wall_time.as_millis() as i64,
+        ),
+        Err(e) => (e.verdict(), String::new(), 0),
+    };
+
+    let mut run = Run {
+        id: 0,
+        user_id: *user_id.as_u64(),
+        task_id: program_name.to_string(),
+        input: input.to_string(),
+        binary_hash: BinaryRunner::binary_hash(program_name).unwrap_or_default(),
+        verdict: verdict.to_string(),
+        output,
+        time_ms,
+        created_at: String::new(),
+    };
+
+    match DATABASE.add_run(&run).await {
+        Ok(id) => run.id = id,
+        Err(e) => error!("Error adding run: {:?}", e),
+    }
+
+    (run, result)
+}
+
+// Calibrated limits are saved next to the bot, workers may have older ones.
+pub async fn run_program(request: &RunRequest) -> RunResult {
+    let time_limit_ms = TaskConfig::load(&request.program).time_limit_ms;
+    let job = Job::Run(request.clone().with_time_limit(time_limit_ms));
+
+    run_job(job).await.and_then(JobOutput::execution)
+}
+
+pub fn format_run(run: &Run, result: Result<Execution, RunnerError>) -> (String, Vec<NamedFile>) {
+    let (output, attached) = match result {
+        Ok(execution) => {
+            let (shown, attached) = format_files(execution.files);
+
+            if execution.output.is_empty() {
+                (shown.trim_end().to_string(), attached)
+            } else {
+                let output = format!("```\n{}\n```\n{}", execution.output, shown);
+                (output.trim_end().to_string(), attached)
+            }
+        }
+        Err(e) => (runner_error_message(e, &run.task_id), Vec::new()),
+    };
+
+    if run.id == 0 {
+        return (output, attached);
+    }
+
+    (format!("{}\n{}", output, run_id_message(run.id)), attached)
+}
+
+fn list_runs(runs: &[Run]) -> String {
+    if runs.is_empty() {
+        return no_runs_message();
+    }
+
+    let mut header = runs_header_message();
+
+    for run in runs {
+        header.push_str(&format!("\t{}\n", run));
+    }
+
+    header
+}
@@ -0,0 +1,80 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::blackbox::parse_blackbox_command;
+use crate::commands::{reply, run_job};
+use crate::runners::interactive_runner::{Exchange, Interaction, InteractorVerdict};
+use crate::runners::job::{Job, JobOutput};
+use tracing::{debug, info};
+
+const TRANSCRIPT_LIMIT: usize = 1500;
+
+#[group]
+#[commands(interact)]
+struct Interactive;
+
+#[command]
+pub async fn interact(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let output = interact_output(ctx, msg, args.rest()).await;
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+pub async fn interact_output(ctx: &Context, msg: &Message, rest: &str) -> String {
+    let (content, program_name, input) = parse_blackbox_command(ctx, msg, rest).await;
+    let interactor_name = format!("{}.interactor", program_name);
+
+    debug!("Content: {}", content);
+    info!("Program: {}", program_name);
+    info!("Interactor: {}", interactor_name);
+    info!("Input: {}", input);
+
+    let job = Job::Interact {
+        program: program_name.clone(),
+        interactor: interactor_name,
+        input,
+    };
+
+    match run_job(job).await.and_then(JobOutput::interaction) {
+        Ok(interaction) => format_interaction(&interaction),
+        Err(e) => runner_error_message(e, &program_name),
+    }
+}
+
+fn format_interaction(interaction: &Interaction) -> String {
+    let verdict = match &interaction.verdict {
+        InteractorVerdict::Accepted(comment) => accepted_message(comment),
+        InteractorVerdict::Rejected(comment) => rejected_message(comment),
+    };
+
+    format!(
+        "{}\n```\n{}\n```",
+        verdict,
+        format_transcript(&interaction.transcript)
+    )
+}
+
+fn format_transcript(transcript: &[Exchange]) -> String {
+    let mut lines = String::new();
+
+    for exchange in transcript {
+        let line = format!("{}\n", exchange);
+
+        if lines.len() + line.len() > TRANSCRIPT_LIMIT {
+            lines.push_str("...\n");
+            break;
+        }
+
+        lines.push_str(&line);
+    }
+
+    lines.trim_end().to_string()
+}
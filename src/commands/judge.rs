@@ -0,0 +1,274 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::blackbox::{extract_code_blocks, split_language_tag};
+use crate::commands::deadlines::{load_assignments, rejection_message};
+use crate::commands::diff::render_diff;
+use crate::commands::{
+    code_block, is_administrator, reply, run_job, send_direct_message, send_message,
+};
+use crate::database::judgement_handler::{Judgement, JudgementHandler};
+use crate::database::submission_handler::SubmissionHandler;
+use crate::runners::compiler::Language;
+use crate::runners::digest::sha256_hex;
+use crate::runners::job::{Job, JobOutput};
+use crate::tasks::grader::{Grade, TestResult};
+use crate::tasks::scoring::format_points;
+use crate::tasks::task_config::TaskConfig;
+use crate::tasks::test_suite::{TestCase, TestSuite};
+use crate::tasks::verdict::Verdict;
+use crate::DATABASE;
+use chrono::Utc;
+use tracing::{error, info};
+
+const MAX_BLOCK_CHARS: usize = 400;
+const MAX_HIDDEN_DETAILS: usize = 5;
+
+#[group]
+#[commands(judge)]
+struct Judging;
+
+#[command]
+pub async fn judge(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let rest = args.rest();
+    let task = rest.split_whitespace().next().unwrap_or("").to_string();
+
+    if task.is_empty() {
+        send_message(&ctx, msg, &no_task_message()).await?;
+        return Ok(());
+    }
+
+    let administrator = is_administrator(ctx, msg).await;
+    let assignments = match load_assignments(administrator) {
+        Ok(assignments) => assignments,
+        Err(message) => {
+            send_message(&ctx, msg, &message).await?;
+            return Ok(());
+        }
+    };
+
+    if let Some(message) = rejection_message(&assignments, &task, administrator) {
+        send_message(&ctx, msg, &message).await?;
+        return Ok(());
+    }
+
+    // Administrators can judge the latest `!submit` of a mentioned student,
+    // which is late if it was submitted after the deadline.
+    let (user_id, author, source, language, submitted_at) = match msg.mentions.first() {
+        Some(_) if !administrator => {
+            send_message(&ctx, msg, &judge_others_forbidden_message()).await?;
+            return Ok(());
+        }
+        Some(student) => {
+            let submissions = DATABASE.get_latest_submissions(&task).await;
+            match submissions
+                .into_iter()
+                .find(|s| s.user_id == *student.id.as_u64())
+            {
+                Some(submission) => {
+                    let submitted_at = submission.submitted_at().unwrap_or_else(Utc::now);
+                    (
+                        submission.user_id,
+                        submission.user_name,
+                        submission.source,
+                        submission.language,
+                        submitted_at,
+                    )
+                }
+                None => {
+                    send_message(&ctx, msg, &no_submission_message(&student.name, &task)).await?;
+                    return Ok(());
+                }
+            }
+        }
+        None => match extract_code_blocks(rest).first() {
+            Some(block) => {
+                let (tag, source) = split_language_tag(block);
+                (
+                    *msg.author.id.as_u64(),
+                    msg.author.name.clone(),
+                    source.to_string(),
+                    Language::from_tag(tag.unwrap_or("")),
+                    msg.timestamp.with_timezone(&Utc),
+                )
+            }
+            None => {
+                send_message(&ctx, msg, &no_source_message()).await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let late = assignments.is_late(&task, submitted_at);
+
+    info!(
+        "Judging {:?} submission of '{}' by '{}'",
+        language, task, author
+    );
+
+    let config = TaskConfig::load(&task);
+    let suite = match TestSuite::load(&task, &config) {
+        Ok(suite) => suite,
+        Err(e) => {
+            reply(&ctx, msg, &runner_error_message(e, &task)).await?;
+            return Ok(());
+        }
+    };
+    let job = Job::Judge {
+        submission: author.clone(),
+        task: task.clone(),
+        source: source.clone(),
+        language,
+        time_limit_ms: config.time_limit_ms,
+    };
+
+    let (grade, output) = match run_job(job).await.and_then(JobOutput::grade) {
+        Ok(grade) => {
+            let mut output = format_grade(&task, &suite, &grade);
+            if late {
+                output = format!("{}\n{}", late_submission_message(&task), output);
+            }
+            (grade, output)
+        }
+        Err(e) => (
+            Grade::failed(&author, &suite, Verdict::from_error(&e)),
+            runner_error_message(e, "program"),
+        ),
+    };
+
+    let judgement = Judgement {
+        id: 0,
+        user_id,
+        user_name: author,
+        task_id: task.clone(),
+        source_hash: source_hash(&source),
+        verdict: grade.verdict().to_string(),
+        score: grade.score,
+        max_score: grade.max_score,
+        time_ms: grade.max_time_ms() as i64,
+        memory_kb: grade.max_memory_kb() as i64,
+        late,
+        created_at: String::new(),
+    };
+    if let Err(e) = DATABASE.add_judgement(&judgement).await {
+        error!("Error adding judgement: {:?}", e);
+    }
+
+    reply(&ctx, msg, &output).await?;
+
+    if administrator {
+        send_hidden_failures(ctx, msg, &task, &suite, &grade).await;
+    }
+
+    Ok(())
+}
+
+// Hidden tests show only the verdict, time and memory, the first failed
+// public test also its input and the difference in output.
+pub fn format_grade(task: &str, suite: &TestSuite, grade: &Grade) -> String {
+    let mut output = judge_summary_message(
+        task,
+        &format_points(grade.score),
+        &format_points(grade.max_score),
+        grade.passed(),
+        grade.tests.len(),
+    );
+
+    if grade.subtasks.len() > 1 {
+        for subtask in &grade.subtasks {
+            output.push('\n');
+            output.push_str(&subtask_score_message(
+                &subtask.name,
+                &format_points(subtask.points),
+                &format_points(subtask.max_points),
+                subtask.passed,
+                subtask.tests,
+            ));
+        }
+    }
+
+    for result in &grade.tests {
+        output.push('\n');
+        output.push_str(&test_result_message(
+            &result.test,
+            &result.verdict.to_string(),
+            result.time_ms,
+            result.memory_kb,
+            result.public,
+        ));
+    }
+
+    let failed_public = suite
+        .tests
+        .iter()
+        .zip(&grade.tests)
+        .find(|(test, result)| test.public && result.verdict != Verdict::Accepted);
+
+    if let Some((test, result)) = failed_public {
+        output.push('\n');
+        output.push_str(&test_details(test, result));
+    }
+
+    output
+}
+
+async fn send_hidden_failures(
+    ctx: &Context,
+    msg: &Message,
+    task: &str,
+    suite: &TestSuite,
+    grade: &Grade,
+) {
+    let failures: Vec<(&TestCase, &TestResult)> = suite
+        .tests
+        .iter()
+        .zip(&grade.tests)
+        .filter(|(test, result)| !test.public && result.verdict != Verdict::Accepted)
+        .take(MAX_HIDDEN_DETAILS)
+        .collect();
+
+    for (test, result) in failures {
+        let content = format!(
+            "{}\n{}",
+            hidden_failure_message(task, &test.name, &result.verdict.to_string()),
+            test_details(test, result)
+        );
+
+        if let Err(e) = send_direct_message(ctx, msg, &content).await {
+            error!("Cannot send hidden test details: {:?}", e);
+            return;
+        }
+    }
+}
+
+fn source_hash(source: &str) -> String {
+    sha256_hex(source.as_bytes())
+}
+
+fn test_details(test: &TestCase, result: &TestResult) -> String {
+    let mut details = format!(
+        "{}\n{}",
+        input_message(),
+        code_block(&test.input, MAX_BLOCK_CHARS)
+    );
+
+    if result.verdict == Verdict::WrongAnswer {
+        match render_diff(&test.expected, &result.output) {
+            Some(diff) => details.push_str(&format!("\n{}", diff)),
+            None => details.push_str(&format!(
+                "\n{}\n{}",
+                expected_output_message(),
+                code_block(&test.expected, MAX_BLOCK_CHARS)
+            )),
+        }
+    }
+
+    details
+}
@@ -1,8 +1,20 @@
+use crate::runners::runner_error::RunnerError;
+
 const CRASH_MESSAGE: &str = "wyjebał się, sprawdź poprawność wejścia";
 const TIMEOUT_MESSAGE: &str = "działał zbyt długo, sprawdź poprawność wejścia";
+const MEMORY_LIMIT_MESSAGE: &str = "zużył zbyt dużo pamięci, sprawdź poprawność wejścia";
+const FORBIDDEN_SYSCALL_MESSAGE: &str = "użył zabronionego wywołania systemowego";
 const NOT_FOUND_MESSAGE: &str = "Nie znaleziono";
 const NO_OUTPUT_MESSAGE: &str = "nic nie wypisał, sprawdź poprawność wejścia";
 const NO_INPUT_MESSAGE: &str = "Nie podano wejścia, pamiętaj o ```";
+const INVALID_INPUT_MESSAGE: &str = "Wejście nie spełnia warunków zadania";
+const COMPILATION_ERROR_MESSAGE: &str = "Błąd kompilacji";
+const MEMORY_ERROR_MESSAGE: &str = "Znaleziono błąd pamięci";
+const INVALID_CONFIG_MESSAGE: &str = "Błędna konfiguracja zadania";
+const CORRECT_ANSWER_MESSAGE: &str = "Odpowiedź poprawna";
+const NO_ANSWER_MESSAGE: &str = "Podaj wejście i swoją odpowiedź w dwóch blokach ```";
+const ACCEPTED_MESSAGE: &str = "Interaktor zaakceptował odpowiedź";
+const REJECTED_MESSAGE: &str = "Interaktor odrzucił odpowiedź";
 
 pub fn crash_message(program_name: &str) -> String {
     format!("`{}` {}", program_name, CRASH_MESSAGE)
@@ -12,6 +24,14 @@ pub fn timeout_message(program_name: &str) -> String {
     format!("`{}` {}", program_name, TIMEOUT_MESSAGE)
 }
 
+pub fn memory_limit_message(program_name: &str) -> String {
+    format!("`{}` {}", program_name, MEMORY_LIMIT_MESSAGE)
+}
+
+pub fn forbidden_syscall_message(program_name: &str) -> String {
+    format!("`{}` {}", program_name, FORBIDDEN_SYSCALL_MESSAGE)
+}
+
 pub fn not_found_message(program_name: &str) -> String {
     format!("{} ` {} `", NOT_FOUND_MESSAGE, program_name)
 }
@@ -24,6 +44,221 @@ pub fn no_input_message() -> String {
     NO_INPUT_MESSAGE.to_string()
 }
 
+pub fn invalid_input_message(explanation: &str) -> String {
+    if explanation.is_empty() {
+        INVALID_INPUT_MESSAGE.to_string()
+    } else {
+        format!("{}:\n```\n{}\n```", INVALID_INPUT_MESSAGE, explanation)
+    }
+}
+
+pub fn compilation_error_message(errors: &str) -> String {
+    format!(
+        "{}:\n```\n{}\n```",
+        COMPILATION_ERROR_MESSAGE,
+        shorten_diagnostic(errors)
+    )
+}
+
+pub fn memory_error_message(diagnostic: &str) -> String {
+    format!(
+        "{}:\n```\n{}\n```",
+        MEMORY_ERROR_MESSAGE,
+        shorten_diagnostic(diagnostic)
+    )
+}
+
+pub fn invalid_config_message(explanation: &str) -> String {
+    format!("{}:\n```\n{}\n```", INVALID_CONFIG_MESSAGE, explanation)
+}
+
+fn shorten_diagnostic(diagnostic: &str) -> String {
+    const DIAGNOSTIC_LIMIT: usize = 900;
+
+    if diagnostic.chars().count() <= DIAGNOSTIC_LIMIT {
+        return diagnostic.trim_end().to_string();
+    }
+
+    let short: String = diagnostic.chars().take(DIAGNOSTIC_LIMIT).collect();
+    format!("{}\n...", short)
+}
+
+pub fn no_source_message() -> String {
+    "Podaj kod w bloku ```, a wejście w kolejnym".to_string()
+}
+
+pub fn valgrind_unavailable_message() -> String {
+    "Valgrind nie jest zainstalowany".to_string()
+}
+
+pub fn correct_answer_message() -> String {
+    format!("**{}**", CORRECT_ANSWER_MESSAGE)
+}
+
+pub fn no_answer_message() -> String {
+    NO_ANSWER_MESSAGE.to_string()
+}
+
+pub fn mismatch_message(line: usize, column: usize) -> String {
+    format!("Różnica w linii {}, kolumnie {}", line, column)
+}
+
+pub fn diff_summary_message(same: usize, different: usize, missing: usize, extra: usize) -> String {
+    format!(
+        "Zgodne linie: {}, różne: {}, brakujące: {}, nadmiarowe: {}",
+        same, different, missing, extra
+    )
+}
+
+pub fn no_task_message() -> String {
+    "Podaj nazwę zadania".to_string()
+}
+
+pub fn generated_message(task: &str, size: u32, seed: u64) -> String {
+    format!(
+        "Test do `{}` (rozmiar `{}`, ziarno `{}`):",
+        task, size, seed
+    )
+}
+
+pub fn reference_output_message() -> String {
+    "Wyjście wzorcówki:".to_string()
+}
+
+pub fn accepted_message(comment: &str) -> String {
+    verdict_message(ACCEPTED_MESSAGE, comment)
+}
+
+pub fn rejected_message(comment: &str) -> String {
+    verdict_message(REJECTED_MESSAGE, comment)
+}
+
+fn verdict_message(verdict: &str, comment: &str) -> String {
+    if comment.is_empty() {
+        format!("**{}**", verdict)
+    } else {
+        format!("**{}**: {}", verdict, comment)
+    }
+}
+
+pub fn runner_error_message(error: RunnerError, program_name: &str) -> String {
+    match error {
+        RunnerError::NoInput => no_input_message(),
+        RunnerError::Timeout => timeout_message(program_name),
+        RunnerError::MemoryLimitExceeded => memory_limit_message(program_name),
+        RunnerError::ForbiddenSyscall => forbidden_syscall_message(program_name),
+        RunnerError::NotFound => not_found_message(program_name),
+        RunnerError::NoOutput => no_output_message(program_name),
+        RunnerError::Crash => crash_message(program_name),
+        RunnerError::InvalidInput(e) => invalid_input_message(&e),
+        RunnerError::CompilationError(e) => compilation_error_message(&e),
+        RunnerError::MemoryError(e) => memory_error_message(&e),
+        RunnerError::InvalidConfig(e) => invalid_config_message(&e),
+        RunnerError::Other(e) => e,
+    }
+}
+
+pub fn run_id_message(run_id: i64) -> String {
+    format!("Uruchomienie `#{}`", run_id)
+}
+
+pub fn invalid_run_id_message() -> String {
+    "Musisz podać numer uruchomienia".to_string()
+}
+
+pub fn run_not_found_message(run_id: i64) -> String {
+    format!("Nie znaleziono uruchomienia `#{}`", run_id)
+}
+
+pub fn binary_changed_message() -> String {
+    "Wzorcówka zmieniła się od poprzedniego uruchomienia".to_string()
+}
+
+pub fn no_runs_message() -> String {
+    "Brak uruchomień".to_string()
+}
+
+pub fn runs_header_message() -> String {
+    "Ostatnie uruchomienia:\n".to_string()
+}
+
+pub fn bench_header_message(runs: usize, warmup_runs: usize) -> String {
+    format!(
+        "Wyniki z {} uruchomień (pominięto {} na rozgrzewkę):",
+        runs, warmup_runs
+    )
+}
+
+pub fn complexity_message(complexity: &str) -> String {
+    format!(
+        "Najlepiej pasuje **{}** (`*` pomiar, `·` dopasowanie):",
+        complexity
+    )
+}
+
+pub fn invalid_multiplier_message() -> String {
+    "Mnożnik musi być liczbą dodatnią".to_string()
+}
+
+pub fn calibration_message(
+    task: &str,
+    tests: usize,
+    worst_test: &str,
+    worst_ms: u128,
+    limit_ms: u128,
+) -> String {
+    format!(
+        "Wzorcówka `{}` przeszła {} testów, najdłużej {} ms na `{}`. Nowy limit czasu: **{} ms**",
+        task, tests, worst_ms, worst_test, limit_ms
+    )
+}
+
+pub fn too_few_measurements_message() -> String {
+    "Za mało pomiarów, żeby oszacować złożoność".to_string()
+}
+
+pub fn submission_saved_message(submission_id: i64, task: &str) -> String {
+    format!("Zapisano rozwiązanie `#{}` do `{}`", submission_id, task)
+}
+
+pub fn submission_not_saved_message() -> String {
+    "Nie udało się zapisać rozwiązania".to_string()
+}
+
+pub fn no_similar_submissions_message(task: &str, submissions: usize) -> String {
+    format!(
+        "Brak podobnych rozwiązań `{}` (sprawdzono {})",
+        task, submissions
+    )
+}
+
+pub fn similar_submissions_message(task: &str, pairs: usize, submissions: usize) -> String {
+    format!(
+        "Podobne rozwiązania `{}`: {} par (sprawdzono {})",
+        task, pairs, submissions
+    )
+}
+
+pub fn report_sent_message() -> String {
+    "Wysłano raport w wiadomości prywatnej".to_string()
+}
+
+pub fn direct_message_failed_message() -> String {
+    "Nie udało się wysłać wiadomości prywatnej, sprawdź ustawienia prywatności".to_string()
+}
+
+pub fn full_output_message() -> String {
+    "Pełne wyjście:".to_string()
+}
+
+pub fn output_file_message(name: &str) -> String {
+    format!("Plik `{}`:", name)
+}
+
+pub fn attached_file_message(name: &str) -> String {
+    format!("Plik `{}` w załączniku", name)
+}
+
 pub fn add_protip_message(_content: &str, task: &str) -> String {
     format!("Dodano protip do `{}`", task)
 }
@@ -39,3 +274,140 @@ pub fn invalid_protip_id_message() -> String {
 pub fn delete_protip_message(protip_id: &u32) -> String {
     format!("Usunięto protip nr {}", protip_id)
 }
+
+pub fn judge_summary_message(
+    task: &str,
+    points: &str,
+    max_points: &str,
+    passed: usize,
+    tests: usize,
+) -> String {
+    format!(
+        "Zadanie `{}`: **{}/{}** pkt, zaliczono {}/{} testów",
+        task, points, max_points, passed, tests
+    )
+}
+
+pub fn subtask_score_message(
+    subtask: &str,
+    points: &str,
+    max_points: &str,
+    passed: usize,
+    tests: usize,
+) -> String {
+    format!(
+        "Podzadanie `{}`: **{}/{}** pkt ({}/{} testów)",
+        subtask, points, max_points, passed, tests
+    )
+}
+
+pub fn test_result_message(
+    test: &str,
+    verdict: &str,
+    time_ms: u128,
+    memory_kb: u64,
+    public: bool,
+) -> String {
+    let hidden = if public { "" } else { " (ukryty)" };
+
+    format!(
+        "`{}`{}: **{}** {} ms, {} KB",
+        test, hidden, verdict, time_ms, memory_kb
+    )
+}
+
+pub fn input_message() -> String {
+    "Wejście:".to_string()
+}
+
+pub fn expected_output_message() -> String {
+    "Oczekiwane wyjście:".to_string()
+}
+
+pub fn hidden_failure_message(task: &str, test: &str, verdict: &str) -> String {
+    format!("Ukryty test `{}` zadania `{}`: **{}**", test, task, verdict)
+}
+
+pub fn judge_others_forbidden_message() -> String {
+    "Tylko administratorzy mogą sprawdzać cudze rozwiązania".to_string()
+}
+
+pub fn no_submission_message(user: &str, task: &str) -> String {
+    format!("Brak rozwiązania `{}` od {}", task, user)
+}
+
+pub fn ranking_header_message(task: &str) -> String {
+    format!("Ranking `{}`:", task)
+}
+
+pub fn ranking_entry_message(
+    position: usize,
+    name: &str,
+    points: &str,
+    max_points: &str,
+    time_ms: i64,
+    late: bool,
+) -> String {
+    let late = if late { late_message() } else { String::new() };
+
+    format!(
+        "{}. **{}** {}/{} pkt, {} ms{}",
+        position, name, points, max_points, time_ms, late
+    )
+}
+
+pub fn anonymous_name_message() -> String {
+    "anonim".to_string()
+}
+
+pub fn empty_ranking_message(task: &str) -> String {
+    format!("Nikt jeszcze nie oddał `{}`", task)
+}
+
+pub fn ranking_hidden_message(task: &str) -> String {
+    format!("Ranking `{}` jest ukryty", task)
+}
+
+pub fn ranking_visibility_message(task: &str, visibility: &str) -> String {
+    format!("Ranking `{}`: {}", task, visibility)
+}
+
+pub fn ranking_visibility_not_saved_message() -> String {
+    "Nie udało się zmienić widoczności rankingu".to_string()
+}
+
+pub fn invalid_ranking_visibility_message() -> String {
+    "Podaj zadanie i widoczność rankingu: `public`, `anonymous` lub `hidden`".to_string()
+}
+
+pub fn late_message() -> String {
+    " (po terminie)".to_string()
+}
+
+pub fn late_submission_message(task: &str) -> String {
+    format!("Rozwiązanie `{}` oddano po terminie", task)
+}
+
+pub fn deadline_not_open_message(task: &str, opens: &str) -> String {
+    format!("Zadanie `{}` można oddawać od {}", task, opens)
+}
+
+pub fn deadline_closed_message(task: &str, closes: &str) -> String {
+    format!("Termin oddania `{}` minął {}", task, closes)
+}
+
+pub fn deadlines_header_message() -> String {
+    "Nadchodzące terminy:".to_string()
+}
+
+pub fn deadline_entry_message(assignment: &str, tasks: &str, closes: &str) -> String {
+    format!("**{}** (`{}`): do {}", assignment, tasks, closes)
+}
+
+pub fn no_deadlines_message() -> String {
+    "Brak nadchodzących terminów".to_string()
+}
+
+pub fn invalid_assignments_message() -> String {
+    "Nie można odczytać terminów zadań, zgłoszenia są wstrzymane".to_string()
+}
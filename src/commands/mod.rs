@@ -1,12 +1,37 @@
 use serenity::model::prelude::Message;
 use serenity::prelude::Context;
-use serenity::utils::ContentSafeOptions;
+use serenity::utils::{ContentSafeOptions, MessageBuilder};
+use tokio::task;
 use tracing::debug;
 
+use crate::commands::reactions::add_controls;
+use crate::runners::job::{Job, JobResult};
+use crate::runners::runner_error::RunnerError;
+use crate::{REPLIES, WORKERS};
+
+pub mod bench;
 pub mod blackbox;
+pub mod calibrate;
+pub mod complexity;
+pub mod deadlines;
+pub mod diff;
+pub mod edit;
+pub mod files;
+pub mod generator;
 pub mod help;
+pub mod history;
+pub mod interact;
+pub mod judge;
 pub mod locale;
 pub mod protip;
+pub mod ranking;
+pub mod reactions;
+pub mod sanitize;
+pub mod submission;
+
+pub const PREFIX: &str = "!";
+pub const MESSAGE_LIMIT: usize = 2000;
+const SHORT_REPLY_LIMIT: usize = 1000;
 
 pub async fn send_message(
     ctx: &Context,
@@ -17,6 +42,62 @@ pub async fn send_message(
     msg.channel_id.say(&ctx.http, content).await
 }
 
+// Longer content is cut to the length of a single message.
+pub async fn send_direct_message(
+    ctx: &Context,
+    msg: &Message,
+    content: &str,
+) -> serenity::Result<Message> {
+    let content: String = content.chars().take(MESSAGE_LIMIT).collect();
+
+    debug!("Sent privately: {}", content);
+    msg.author.direct_message(ctx, |m| m.content(content)).await
+}
+
+pub async fn reply(ctx: &Context, msg: &Message, output: &str) -> serenity::Result<Message> {
+    let content = mention_reply(msg, output);
+    let short = shorten_reply(&content);
+    let truncated = short.is_some();
+
+    let reply = send_message(ctx, msg, short.as_ref().unwrap_or(&content)).await?;
+
+    REPLIES.track(msg, &reply, if truncated { Some(content) } else { None });
+    add_controls(ctx, msg, &reply, truncated).await;
+    Ok(reply)
+}
+
+pub async fn is_administrator(ctx: &Context, msg: &Message) -> bool {
+    match msg.member(ctx).await {
+        Ok(member) => member
+            .permissions(ctx)
+            .await
+            .map(|permissions| permissions.administrator())
+            .unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+pub fn shorten_reply(content: &str) -> Option<String> {
+    if content.chars().count() <= SHORT_REPLY_LIMIT {
+        return None;
+    }
+
+    let mut short: String = content.chars().take(SHORT_REPLY_LIMIT).collect();
+
+    if short.matches("```").count() % 2 == 1 {
+        short.push_str("\n...\n```");
+    } else {
+        short.push_str("\n...");
+    }
+
+    Some(short)
+}
+
+pub fn mention_reply(msg: &Message, output: &str) -> String {
+    let content = MessageBuilder::new().user(&msg.author).build();
+    format!("{}\n{}", content, output)
+}
+
 pub fn make_settings(msg: &Message) -> ContentSafeOptions {
     if let Some(guild_id) = msg.guild_id {
         ContentSafeOptions::default()
@@ -28,3 +109,25 @@ pub fn make_settings(msg: &Message) -> ContentSafeOptions {
             .clean_role(false)
     }
 }
+
+pub fn code_block(content: &str, limit: usize) -> String {
+    if content.chars().count() <= limit {
+        return format!("```\n{}\n```", content);
+    }
+
+    let truncated: String = content.chars().take(limit).collect();
+    format!("```\n{}\n...\n```", truncated)
+}
+
+// Runs code of users on remote workers when they are configured, locally otherwise.
+// Jobs take up to minutes, so they wait on a blocking thread instead of stalling
+// the executor that handles every other command.
+pub async fn run_job(job: Job) -> JobResult {
+    let finished = task::spawn_blocking(move || match WORKERS.as_ref() {
+        Some(pool) => pool.execute(&job),
+        None => job.execute(),
+    })
+    .await;
+
+    finished.unwrap_or_else(|e| Err(RunnerError::Other(e.to_string())))
+}
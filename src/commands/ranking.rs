@@ -0,0 +1,129 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::{is_administrator, reply, send_direct_message, send_message};
+use crate::database::judgement_handler::{Judgement, JudgementHandler, RankingVisibility};
+use crate::tasks::scoring::format_points;
+use crate::DATABASE;
+use tracing::error;
+
+const RANKING_LENGTH: u32 = 20;
+
+#[group]
+#[commands(ranking, rankingmode)]
+struct Rankings;
+
+#[command]
+#[delimiters(' ')]
+pub async fn ranking(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let task = match args.single::<String>() {
+        Ok(task) => task,
+        Err(_) => {
+            send_message(&ctx, msg, &no_task_message()).await?;
+            return Ok(());
+        }
+    };
+
+    // Administrators always see the full ranking, but only privately.
+    if is_administrator(ctx, msg).await {
+        let judgements = DATABASE.get_ranking(&task, RANKING_LENGTH).await;
+        let output = format_ranking(
+            &task,
+            &judgements,
+            RankingVisibility::Public,
+            *msg.author.id.as_u64(),
+        );
+
+        if msg.guild_id.is_none() {
+            reply(&ctx, msg, &output).await?;
+        } else if let Err(e) = send_direct_message(ctx, msg, &output).await {
+            error!("Cannot send ranking: {:?}", e);
+            send_message(&ctx, msg, &direct_message_failed_message()).await?;
+        } else {
+            send_message(&ctx, msg, &report_sent_message()).await?;
+        }
+
+        return Ok(());
+    }
+
+    let visibility = DATABASE.get_ranking_visibility(&task).await;
+
+    if visibility == RankingVisibility::Hidden {
+        send_message(&ctx, msg, &ranking_hidden_message(&task)).await?;
+        return Ok(());
+    }
+
+    let judgements = DATABASE.get_ranking(&task, RANKING_LENGTH).await;
+    let output = format_ranking(&task, &judgements, visibility, *msg.author.id.as_u64());
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+#[command]
+#[required_permissions(ADMINISTRATOR)]
+#[delimiters(' ')]
+pub async fn rankingmode(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let task = args.single::<String>().ok();
+    let visibility = args
+        .single::<String>()
+        .ok()
+        .and_then(|name| RankingVisibility::parse(&name));
+
+    let output = match (task, visibility) {
+        (Some(task), Some(visibility)) => {
+            match DATABASE.set_ranking_visibility(&task, visibility).await {
+                Ok(()) => ranking_visibility_message(&task, visibility.name()),
+                Err(e) => {
+                    error!("Error setting ranking visibility: {:?}", e);
+                    ranking_visibility_not_saved_message()
+                }
+            }
+        }
+        _ => invalid_ranking_visibility_message(),
+    };
+
+    send_message(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+// Anonymous rankings show only the name of the user asking.
+fn format_ranking(
+    task: &str,
+    judgements: &[Judgement],
+    visibility: RankingVisibility,
+    user_id: u64,
+) -> String {
+    if judgements.is_empty() {
+        return empty_ranking_message(task);
+    }
+
+    let mut output = ranking_header_message(task);
+
+    for (position, judgement) in judgements.iter().enumerate() {
+        let name = if visibility == RankingVisibility::Anonymous && judgement.user_id != user_id {
+            anonymous_name_message()
+        } else {
+            judgement.user_name.clone()
+        };
+
+        output.push('\n');
+        output.push_str(&ranking_entry_message(
+            position + 1,
+            &name,
+            &format_points(judgement.score),
+            &format_points(judgement.max_score),
+            judgement.time_ms,
+            judgement.late,
+        ));
+    }
+
+    output
+}
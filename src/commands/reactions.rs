@@ -0,0 +1,105 @@
+use serenity::model::channel::{Message, Reaction, ReactionType};
+use serenity::prelude::*;
+
+use crate::commands::edit::{is_rerunnable, update_reply};
+use crate::commands::locale::full_output_message;
+use crate::commands::MESSAGE_LIMIT;
+use crate::replies::reply_tracker::TrackedReply;
+use crate::REPLIES;
+use tracing::{error, info, warn};
+
+const RERUN: char = '🔁';
+const DELETE: char = '🗑';
+const EXPAND: char = '➕';
+
+pub async fn add_controls(ctx: &Context, command: &Message, reply: &Message, truncated: bool) {
+    let mut controls = Vec::new();
+
+    if is_rerunnable(command) {
+        controls.push(RERUN);
+    }
+    controls.push(DELETE);
+    if truncated {
+        controls.push(EXPAND);
+    }
+
+    for control in controls {
+        if let Err(e) = reply.react(&ctx.http, control).await {
+            warn!("Could not add {} to reply: {:?}", control, e);
+        }
+    }
+}
+
+pub async fn handle_reaction(ctx: &Context, reaction: &Reaction) {
+    let reply = match REPLIES.find_by_reply(reaction.message_id) {
+        Some(reply) => reply,
+        None => return,
+    };
+
+    if reaction.user_id != Some(reply.user_id) {
+        return;
+    }
+
+    let control = match &reaction.emoji {
+        ReactionType::Unicode(emoji) => emoji.trim_end_matches('\u{fe0f}').chars().next(),
+        _ => None,
+    };
+
+    match control {
+        Some(RERUN) => rerun(ctx, reply).await,
+        Some(DELETE) => delete(ctx, reply).await,
+        Some(EXPAND) => expand(ctx, reply).await,
+        _ => {}
+    }
+}
+
+async fn rerun(ctx: &Context, reply: TrackedReply) {
+    match reply.channel_id.message(&ctx.http, reply.command_id).await {
+        Ok(command) => {
+            info!("Re-running command by user '{}'", command.author.name);
+            update_reply(ctx, &command, reply).await;
+        }
+        Err(e) => warn!("Could not fetch command message: {:?}", e),
+    }
+}
+
+async fn delete(ctx: &Context, reply: TrackedReply) {
+    info!("Deleting reply {}", reply.reply_id);
+
+    REPLIES.untrack(reply.command_id);
+    if let Err(e) = reply
+        .channel_id
+        .delete_message(&ctx.http, reply.reply_id)
+        .await
+    {
+        error!("Error deleting reply: {:?}", e);
+    }
+}
+
+async fn expand(ctx: &Context, reply: TrackedReply) {
+    let content = match reply.full_reply {
+        Some(content) => content,
+        None => return,
+    };
+
+    info!("Expanding reply {}", reply.reply_id);
+    REPLIES.set_full_reply(reply.command_id, None);
+
+    let result = if content.chars().count() <= MESSAGE_LIMIT {
+        reply
+            .channel_id
+            .edit_message(&ctx.http, reply.reply_id, |m| m.content(&content))
+            .await
+    } else {
+        reply
+            .channel_id
+            .send_files(&ctx.http, vec![(content.as_bytes(), "output.txt")], |m| {
+                m.content(full_output_message())
+            })
+            .await
+    };
+
+    if let Err(e) = result {
+        error!("Error expanding reply: {:?}", e);
+    }
+}
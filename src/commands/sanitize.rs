@@ -0,0 +1,65 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+    utils::content_safe,
+};
+
+use crate::commands::blackbox::{extract_code_blocks, split_language_tag};
+use crate::commands::{make_settings, reply, run_job};
+use crate::runners::compiler::Language;
+use crate::runners::job::{Job, JobOutput};
+use crate::runners::runner_error::RunnerError;
+use crate::runners::sanitizer::SanitizerMode;
+use tracing::info;
+
+#[group]
+#[commands(sanitize)]
+struct Sanitizers;
+
+#[command]
+pub async fn sanitize(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let output = sanitize_output(ctx, msg, args.rest()).await;
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+pub async fn sanitize_output(ctx: &Context, msg: &Message, rest: &str) -> String {
+    let content = content_safe(&ctx.cache, rest, &make_settings(msg)).await;
+    let mode = match content.split_whitespace().next() {
+        Some("valgrind") => SanitizerMode::Valgrind,
+        _ => SanitizerMode::Sanitizers,
+    };
+
+    let blocks = extract_code_blocks(&content);
+    let (source, input) = match blocks.as_slice() {
+        [source] => (source, ""),
+        [source, input, ..] => (source, input.as_str()),
+        _ => return no_source_message(),
+    };
+    let (tag, source) = split_language_tag(source);
+    let language = Language::from_tag(tag.unwrap_or(""));
+
+    info!("Sanitizing {:?} submission in {:?} mode", language, mode);
+
+    let job = Job::Sanitize {
+        source: source.to_string(),
+        language,
+        input: input.to_string(),
+        mode,
+    };
+
+    // The host running the job may lack valgrind, sanitizers ship with the compiler.
+    match run_job(job).await.and_then(JobOutput::sanitized) {
+        Ok(out) => format!("```\n{}\n```", out),
+        Err(RunnerError::NotFound) if mode == SanitizerMode::Valgrind => {
+            valgrind_unavailable_message()
+        }
+        Err(e) => runner_error_message(e, "program"),
+    }
+}
@@ -0,0 +1,156 @@
+use crate::commands::locale::*;
+use serenity::prelude::*;
+use serenity::{
+    framework::standard::{
+        macros::{command, group},
+        Args, CommandResult,
+    },
+    model::channel::Message,
+};
+
+use crate::commands::blackbox::{extract_code_blocks, split_language_tag};
+use crate::commands::deadlines::{load_assignments, rejection_message};
+use crate::commands::{is_administrator, reply, send_direct_message, send_message};
+use crate::database::submission_handler::{Submission, SubmissionHandler};
+use crate::plagiarism::similarity::{similar_pairs, SimilarPair};
+use crate::runners::compiler::Language;
+use crate::DATABASE;
+use tracing::{error, info};
+
+const DEFAULT_THRESHOLD: f64 = 0.8;
+const MAX_PAIRS: usize = 5;
+const MAX_FRAGMENT_LINES: usize = 8;
+
+#[group]
+#[commands(submit, plagiarism)]
+struct Submissions;
+
+#[command]
+pub async fn submit(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let rest = args.rest();
+    let task = rest.split_whitespace().next().unwrap_or("").to_string();
+    let blocks = extract_code_blocks(rest);
+
+    let (tag, source) = match (task.is_empty(), blocks.first()) {
+        (false, Some(block)) => split_language_tag(block),
+        _ => {
+            send_message(&ctx, msg, &no_source_message()).await?;
+            return Ok(());
+        }
+    };
+
+    let administrator = is_administrator(ctx, msg).await;
+    let rejection = load_assignments(administrator)
+        .map(|assignments| rejection_message(&assignments, &task, administrator))
+        .unwrap_or_else(Some);
+
+    if let Some(message) = rejection {
+        send_message(&ctx, msg, &message).await?;
+        return Ok(());
+    }
+
+    let submission = Submission {
+        id: 0,
+        user_id: *msg.author.id.as_u64(),
+        user_name: msg.author.name.clone(),
+        task_id: task,
+        source: source.to_string(),
+        language: Language::from_tag(tag.unwrap_or("")),
+        created_at: String::new(),
+    };
+
+    let output = match DATABASE.add_submission(&submission).await {
+        Ok(id) => submission_saved_message(id, &submission.task_id),
+        Err(e) => {
+            error!("Error adding submission: {:?}", e);
+            submission_not_saved_message()
+        }
+    };
+
+    reply(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+#[command]
+#[required_permissions(ADMINISTRATOR)]
+#[delimiters(' ')]
+pub async fn plagiarism(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let task = match args.single::<String>() {
+        Ok(task) => task,
+        Err(_) => {
+            send_message(&ctx, msg, &no_task_message()).await?;
+            return Ok(());
+        }
+    };
+    let threshold = args
+        .single::<f64>()
+        .map(|percent| percent / 100.0)
+        .unwrap_or(DEFAULT_THRESHOLD);
+
+    let submissions = DATABASE.get_latest_submissions(&task).await;
+    let sources: Vec<&str> = submissions.iter().map(|s| s.source.as_str()).collect();
+    let pairs = similar_pairs(&sources, threshold);
+
+    info!(
+        "Found {} similar pairs among {} submissions of '{}'",
+        pairs.len(),
+        submissions.len(),
+        task
+    );
+
+    // Names and code of students are not shown in the channel.
+    let mut output = report_sent_message();
+
+    for part in format_report(&task, &submissions, &pairs) {
+        if let Err(e) = send_direct_message(ctx, msg, &part).await {
+            error!("Cannot send plagiarism report: {:?}", e);
+            output = direct_message_failed_message();
+            break;
+        }
+    }
+
+    send_message(&ctx, msg, &output).await?;
+    Ok(())
+}
+
+// Every pair goes to a separate message, as its fragment can be long.
+fn format_report(task: &str, submissions: &[Submission], pairs: &[SimilarPair]) -> Vec<String> {
+    if pairs.is_empty() {
+        return vec![no_similar_submissions_message(task, submissions.len())];
+    }
+
+    let mut report = vec![similar_submissions_message(
+        task,
+        pairs.len(),
+        submissions.len(),
+    )];
+
+    for pair in pairs.iter().take(MAX_PAIRS) {
+        let first = &submissions[pair.first];
+        let second = &submissions[pair.second];
+
+        report.push(format!(
+            "**{}** ↔ **{}**: {:.0}% ({}-{} ↔ {}-{})\n```\n{}\n```",
+            first.user_name,
+            second.user_name,
+            pair.similarity * 100.0,
+            pair.first_lines.0,
+            pair.first_lines.1,
+            pair.second_lines.0,
+            pair.second_lines.1,
+            fragment(&first.source, pair.first_lines)
+        ));
+    }
+
+    report
+}
+
+fn fragment(source: &str, (first, last): (usize, usize)) -> String {
+    let lines: Vec<&str> = source
+        .lines()
+        .skip(first.saturating_sub(1))
+        .take((last + 1).saturating_sub(first).min(MAX_FRAGMENT_LINES))
+        .collect();
+
+    lines.join("\n").replace("```", "'''")
+}
@@ -0,0 +1,209 @@
+use crate::database::database_connection::DatabaseConnection;
+use crate::database::Database;
+use crate::{BoxError, BoxResult};
+use async_trait::async_trait;
+use rusqlite::{params, Row};
+use tracing::{debug, info};
+
+#[derive(Debug)]
+pub struct Judgement {
+    pub id: i64,
+    pub user_id: u64,
+    pub user_name: String,
+    pub task_id: String,
+    pub source_hash: String,
+    pub verdict: String,
+    pub score: f64,
+    pub max_score: f64,
+    pub time_ms: i64,
+    pub memory_kb: i64,
+    pub late: bool,
+    pub created_at: String,
+}
+
+impl Judgement {
+    fn from_row(row: &Row) -> rusqlite::Result<Judgement> {
+        let user_id: i64 = row.get(1)?;
+
+        Ok(Judgement {
+            id: row.get(0)?,
+            user_id: user_id as u64,
+            user_name: row.get(2)?,
+            task_id: row.get(3)?,
+            source_hash: row.get(4)?,
+            verdict: row.get(5)?,
+            score: row.get(6)?,
+            max_score: row.get(7)?,
+            time_ms: row.get(8)?,
+            memory_kb: row.get(9)?,
+            late: row.get(10)?,
+            created_at: row.get(11)?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RankingVisibility {
+    Public,
+    Anonymous,
+    Hidden,
+}
+
+impl RankingVisibility {
+    pub fn parse(name: &str) -> Option<RankingVisibility> {
+        match name {
+            "public" => Some(RankingVisibility::Public),
+            "anonymous" => Some(RankingVisibility::Anonymous),
+            "hidden" => Some(RankingVisibility::Hidden),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RankingVisibility::Public => "public",
+            RankingVisibility::Anonymous => "anonymous",
+            RankingVisibility::Hidden => "hidden",
+        }
+    }
+}
+
+const JUDGEMENT_COLUMNS: &str = "id, user_id, user_name, task_id, source_hash, verdict, score, \
+     max_score, time_ms, memory_kb, late, created_at";
+
+#[async_trait]
+pub trait JudgementHandler {
+    async fn set_up_judgement_table(&self) -> BoxResult;
+    async fn add_judgement(&self, judgement: &Judgement) -> Result<i64, BoxError>;
+    async fn get_ranking(&self, task_id: &str, limit: u32) -> Vec<Judgement>;
+    async fn get_ranking_visibility(&self, task_id: &str) -> RankingVisibility;
+    async fn set_ranking_visibility(
+        &self,
+        task_id: &str,
+        visibility: RankingVisibility,
+    ) -> BoxResult;
+}
+
+#[async_trait]
+impl<T: DatabaseConnection + std::marker::Send> JudgementHandler for Database<T> {
+    async fn set_up_judgement_table(&self) -> BoxResult {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS judgement (
+                  id              INTEGER PRIMARY KEY,
+                  user_id         INTEGER NOT NULL,
+                  user_name       TEXT NOT NULL,
+                  task_id         TEXT NOT NULL,
+                  source_hash     TEXT NOT NULL,
+                  verdict         TEXT NOT NULL,
+                  score           REAL NOT NULL,
+                  max_score       REAL NOT NULL,
+                  time_ms         INTEGER NOT NULL,
+                  memory_kb       INTEGER NOT NULL,
+                  late            INTEGER NOT NULL DEFAULT 0,
+                  created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                  )",
+        )
+        .await?;
+        self.add_column("judgement", "late", "INTEGER NOT NULL DEFAULT 0")
+            .await?;
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS ranking (
+                  task_id         TEXT PRIMARY KEY,
+                  visibility      TEXT NOT NULL
+                  )",
+        )
+        .await?;
+
+        info!("Judgement database initialized");
+        Ok(())
+    }
+
+    async fn add_judgement(&self, judgement: &Judgement) -> Result<i64, BoxError> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        conn.execute(
+            "INSERT INTO judgement (user_id, user_name, task_id, source_hash, verdict, score,
+                  max_score, time_ms, memory_kb, late)
+                  VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                judgement.user_id as i64,
+                judgement.user_name,
+                judgement.task_id,
+                judgement.source_hash,
+                judgement.verdict,
+                judgement.score,
+                judgement.max_score,
+                judgement.time_ms,
+                judgement.memory_kb,
+                judgement.late
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        info!(
+            "Added judgement {} of task '{}' by '{}'",
+            id, judgement.task_id, judgement.user_name
+        );
+        Ok(id)
+    }
+
+    // The best judgement of every user, by score and then by time.
+    async fn get_ranking(&self, task_id: &str, limit: u32) -> Vec<Judgement> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM (
+                      SELECT *, ROW_NUMBER() OVER (
+                          PARTITION BY user_id ORDER BY score DESC, time_ms ASC, id ASC
+                      ) AS position FROM judgement WHERE task_id = ?1
+                  ) WHERE position = 1 ORDER BY score DESC, time_ms ASC, id ASC LIMIT ?2",
+                JUDGEMENT_COLUMNS
+            ))
+            .unwrap();
+        let judgement_iter = stmt
+            .query_map(params![task_id, limit], Judgement::from_row)
+            .unwrap();
+
+        let mut judgements = Vec::new();
+
+        for judgement in judgement_iter {
+            debug!("Found judgement {:?}", judgement.as_ref().unwrap().id);
+            judgements.push(judgement.unwrap());
+        }
+
+        judgements
+    }
+
+    async fn get_ranking_visibility(&self, task_id: &str) -> RankingVisibility {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        conn.query_row(
+            "SELECT visibility FROM ranking WHERE task_id = ?1",
+            params![task_id],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|name| RankingVisibility::parse(&name))
+        .unwrap_or(RankingVisibility::Public)
+    }
+
+    async fn set_ranking_visibility(
+        &self,
+        task_id: &str,
+        visibility: RankingVisibility,
+    ) -> BoxResult {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO ranking (task_id, visibility) VALUES (?1, ?2)",
+            params![task_id, visibility.name()],
+        )?;
+
+        info!("Ranking of '{}' is {}", task_id, visibility.name());
+        Ok(())
+    }
+}
@@ -1,11 +1,16 @@
 pub mod database_connection;
+pub mod judgement_handler;
 pub mod protip_handler;
+pub mod run_handler;
 pub mod sqlite_connection;
+pub mod submission_handler;
 
 use crate::database::database_connection::DatabaseConnection;
 use crate::BoxResult;
 
+use rusqlite::params;
 use tokio::sync::Mutex;
+use tracing::info;
 
 pub struct Database<T: DatabaseConnection> {
     mutex: Mutex<T>,
@@ -27,4 +32,27 @@ impl<T: DatabaseConnection> Database<T> {
         let db = self.mutex.lock().await;
         db.execute(query)
     }
+
+    // Tables created by older versions do not get new columns from
+    // `CREATE TABLE IF NOT EXISTS`.
+    async fn add_column(&self, table: &str, column: &str, definition: &str) -> BoxResult {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+            params![table, column],
+            |row| row.get(0),
+        )?;
+
+        if !exists {
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                params![],
+            )?;
+            info!("Added column {} to table {}", column, table);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,145 @@
+use crate::database::database_connection::DatabaseConnection;
+use crate::database::Database;
+use crate::{BoxError, BoxResult};
+use async_trait::async_trait;
+use rusqlite::{params, Row};
+use std::fmt;
+use tracing::{debug, info};
+
+#[derive(Debug)]
+pub struct Run {
+    pub id: i64,
+    pub user_id: u64,
+    pub task_id: String,
+    pub input: String,
+    pub binary_hash: String,
+    pub verdict: String,
+    pub output: String,
+    pub time_ms: i64,
+    pub created_at: String,
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} `{}` {} {} ms ({})",
+            self.id, self.task_id, self.verdict, self.time_ms, self.created_at
+        )
+    }
+}
+
+impl Run {
+    fn from_row(row: &Row) -> rusqlite::Result<Run> {
+        let user_id: i64 = row.get(1)?;
+
+        Ok(Run {
+            id: row.get(0)?,
+            user_id: user_id as u64,
+            task_id: row.get(2)?,
+            input: row.get(3)?,
+            binary_hash: row.get(4)?,
+            verdict: row.get(5)?,
+            output: row.get(6)?,
+            time_ms: row.get(7)?,
+            created_at: row.get(8)?,
+        })
+    }
+}
+
+const RUN_COLUMNS: &str =
+    "id, user_id, task_id, input, binary_hash, verdict, output, time_ms, created_at";
+
+#[async_trait]
+pub trait RunHandler {
+    async fn set_up_run_table(&self) -> BoxResult;
+    async fn add_run(&self, run: &Run) -> Result<i64, BoxError>;
+    async fn get_run(&self, run_id: i64) -> Option<Run>;
+    async fn get_runs(&self, user_id: u64, task_id: Option<&str>, limit: u32) -> Vec<Run>;
+}
+
+#[async_trait]
+impl<T: DatabaseConnection + std::marker::Send> RunHandler for Database<T> {
+    async fn set_up_run_table(&self) -> BoxResult {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS run (
+                  id              INTEGER PRIMARY KEY,
+                  user_id         INTEGER NOT NULL,
+                  task_id         TEXT NOT NULL,
+                  input           TEXT NOT NULL,
+                  binary_hash     TEXT NOT NULL,
+                  verdict         TEXT NOT NULL,
+                  output          TEXT NOT NULL,
+                  time_ms         INTEGER NOT NULL,
+                  created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                  )",
+        )
+        .await?;
+
+        info!("Run history database initialized");
+        Ok(())
+    }
+
+    async fn add_run(&self, run: &Run) -> Result<i64, BoxError> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        conn.execute(
+            "INSERT INTO run (user_id, task_id, input, binary_hash, verdict, output, time_ms)
+                  VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run.user_id as i64,
+                run.task_id,
+                run.input,
+                run.binary_hash,
+                run.verdict,
+                run.output,
+                run.time_ms
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        info!("Added run {} of task '{}'", id, run.task_id);
+        Ok(id)
+    }
+
+    async fn get_run(&self, run_id: i64) -> Option<Run> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        let run = conn
+            .query_row(
+                &format!("SELECT {} FROM run WHERE id = ?1", RUN_COLUMNS),
+                params![run_id],
+                Run::from_row,
+            )
+            .ok();
+
+        debug!("Found run {:?}", run);
+        run
+    }
+
+    async fn get_runs(&self, user_id: u64, task_id: Option<&str>, limit: u32) -> Vec<Run> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM run WHERE user_id = ?1 AND (?2 IS NULL OR task_id = ?2)
+                      ORDER BY id DESC LIMIT ?3",
+                RUN_COLUMNS
+            ))
+            .unwrap();
+        let run_iter = stmt
+            .query_map(params![user_id as i64, task_id, limit], Run::from_row)
+            .unwrap();
+
+        let mut runs = Vec::new();
+
+        for run in run_iter {
+            debug!("Found run {:?}", run.as_ref().unwrap());
+            runs.push(run.unwrap());
+        }
+
+        runs
+    }
+}
@@ -0,0 +1,121 @@
+use crate::database::database_connection::DatabaseConnection;
+use crate::database::Database;
+use crate::runners::compiler::Language;
+use crate::{BoxError, BoxResult};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use rusqlite::{params, Row};
+use tracing::{debug, info};
+
+#[derive(Debug)]
+pub struct Submission {
+    pub id: i64,
+    pub user_id: u64,
+    pub user_name: String,
+    pub task_id: String,
+    pub source: String,
+    pub language: Language,
+    pub created_at: String,
+}
+
+impl Submission {
+    fn from_row(row: &Row) -> rusqlite::Result<Submission> {
+        let user_id: i64 = row.get(1)?;
+        let language: String = row.get(5)?;
+
+        Ok(Submission {
+            id: row.get(0)?,
+            user_id: user_id as u64,
+            user_name: row.get(2)?,
+            task_id: row.get(3)?,
+            source: row.get(4)?,
+            language: Language::from_tag(&language),
+            created_at: row.get(6)?,
+        })
+    }
+
+    // CURRENT_TIMESTAMP of SQLite, in UTC.
+    pub fn submitted_at(&self) -> Option<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(&self.created_at, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|time| Utc.from_utc_datetime(&time))
+    }
+}
+
+#[async_trait]
+pub trait SubmissionHandler {
+    async fn set_up_submission_table(&self) -> BoxResult;
+    async fn add_submission(&self, submission: &Submission) -> Result<i64, BoxError>;
+    async fn get_latest_submissions(&self, task_id: &str) -> Vec<Submission>;
+}
+
+#[async_trait]
+impl<T: DatabaseConnection + std::marker::Send> SubmissionHandler for Database<T> {
+    async fn set_up_submission_table(&self) -> BoxResult {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS submission (
+                  id              INTEGER PRIMARY KEY,
+                  user_id         INTEGER NOT NULL,
+                  user_name       TEXT NOT NULL,
+                  task_id         TEXT NOT NULL,
+                  source          TEXT NOT NULL,
+                  language        TEXT NOT NULL DEFAULT 'cpp',
+                  created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                  )",
+        )
+        .await?;
+        self.add_column("submission", "language", "TEXT NOT NULL DEFAULT 'cpp'")
+            .await?;
+
+        info!("Submission database initialized");
+        Ok(())
+    }
+
+    async fn add_submission(&self, submission: &Submission) -> Result<i64, BoxError> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+
+        conn.execute(
+            "INSERT INTO submission (user_id, user_name, task_id, source, language)
+                  VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                submission.user_id as i64,
+                submission.user_name,
+                submission.task_id,
+                submission.source,
+                submission.language.tag()
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        info!(
+            "Added submission {} of task '{}' by '{}'",
+            id, submission.task_id, submission.user_name
+        );
+        Ok(id)
+    }
+
+    async fn get_latest_submissions(&self, task_id: &str) -> Vec<Submission> {
+        let db = self.mutex.lock().await;
+        let conn = db.raw();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, user_name, task_id, source, language, created_at FROM submission
+                      WHERE id IN (SELECT MAX(id) FROM submission WHERE task_id = ?1 GROUP BY user_id)
+                      ORDER BY id",
+            )
+            .unwrap();
+        let submission_iter = stmt
+            .query_map(params![task_id], Submission::from_row)
+            .unwrap();
+
+        let mut submissions = Vec::new();
+
+        for submission in submission_iter {
+            debug!("Found submission {:?}", submission.as_ref().unwrap().id);
+            submissions.push(submission.unwrap());
+        }
+
+        submissions
+    }
+}
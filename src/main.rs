@@ -1,16 +1,39 @@
 mod commands;
 mod database;
-mod runners;
+mod plagiarism;
+mod replies;
 mod trigger;
 
+use crate::commands::bench::BENCHMARKS_GROUP;
 use crate::commands::blackbox::BLACKBOX_GROUP;
+use crate::commands::calibrate::CALIBRATION_GROUP;
+use crate::commands::complexity::COMPLEXITY_GROUP;
+use crate::commands::deadlines::DEADLINES_GROUP;
+use crate::commands::edit::handle_edit;
+use crate::commands::generator::GENERATORS_GROUP;
 use crate::commands::help::MY_HELP;
+use crate::commands::history::HISTORY_GROUP;
+use crate::commands::interact::INTERACTIVE_GROUP;
+use crate::commands::judge::JUDGING_GROUP;
 use crate::commands::protip::PROTIP_GROUP;
-use crate::commands::send_message;
+use crate::commands::ranking::RANKINGS_GROUP;
+use crate::commands::reactions::handle_reaction;
+use crate::commands::sanitize::SANITIZERS_GROUP;
+use crate::commands::submission::SUBMISSIONS_GROUP;
+use crate::commands::{send_message, PREFIX};
+use crate::database::judgement_handler::JudgementHandler;
 use crate::database::protip_handler::ProtipHandler;
+use crate::database::run_handler::RunHandler;
 use crate::database::sqlite_connection::SQLiteConnection;
+use crate::database::submission_handler::SubmissionHandler;
 use crate::database::Database;
+use crate::replies::reply_tracker::ReplyTracker;
 use crate::trigger::handle_triggers;
+use janosik_runner::remote::worker_pool::WorkerPool;
+use janosik_runner::runners::process_group::ProcessGroup;
+use janosik_runner::runners::resource_control::ResourceControl;
+use janosik_runner::runners::syscall_filter::SyscallFilter;
+use janosik_runner::{runners, tasks};
 use once_cell::sync::Lazy;
 use serenity::futures::io::ErrorKind;
 use serenity::prelude::*;
@@ -18,11 +41,18 @@ use serenity::{
     async_trait,
     framework::standard::{macros::hook, CommandResult, DispatchError, StandardFramework},
     http::Http,
-    model::{channel::Message, gateway::Ready, id::UserId},
+    model::{
+        channel::{Message, Reaction},
+        event::MessageUpdateEvent,
+        gateway::Ready,
+        id::UserId,
+    },
     Error,
 };
 use std::collections::hash_map::RandomState;
+use std::sync::Arc;
 use std::{collections::HashSet, env, io};
+use tokio::signal::unix::{signal, SignalKind};
 use tracing::{debug, error, info, instrument, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -45,6 +75,41 @@ impl EventHandler for Handler {
         context.set_presence(Some(activity), status).await;
         info!("{} is connected!", ready.user.name);
     }
+
+    async fn message_update(
+        &self,
+        context: Context,
+        _old: Option<Message>,
+        new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        // Embed unfurls and pin changes update the message without new content.
+        let content = match event.content {
+            Some(content) => content,
+            None => return,
+        };
+        let reply = match REPLIES.get(event.id) {
+            Some(reply) if reply.content != content => reply,
+            _ => return,
+        };
+
+        let msg = match new {
+            Some(msg) => msg,
+            None => match event.channel_id.message(&context.http, event.id).await {
+                Ok(msg) => msg,
+                Err(why) => {
+                    warn!("Could not fetch edited message: {:?}", why);
+                    return;
+                }
+            },
+        };
+
+        handle_edit(&context, &msg, reply).await;
+    }
+
+    async fn reaction_add(&self, context: Context, reaction: Reaction) {
+        handle_reaction(&context, &reaction).await;
+    }
 }
 
 #[hook]
@@ -89,6 +154,18 @@ async fn dispatch_error(ctx: &Context, msg: &Message, error: DispatchError) {
 }
 
 static DATABASE: Lazy<Database<SQLiteConnection>> = Lazy::new(Database::new);
+static REPLIES: Lazy<ReplyTracker> = Lazy::new(ReplyTracker::new);
+static WORKERS: Lazy<Option<Arc<WorkerPool>>> = Lazy::new(|| {
+    env::var("JANOSIK_WORKERS")
+        .ok()
+        .and_then(|list| {
+            let token = env::var("JANOSIK_WORKER_TOKEN")
+                .ok()
+                .filter(|t| !t.is_empty());
+            WorkerPool::from_list(&list, token)
+        })
+        .map(Arc::new)
+});
 
 type BoxError = Box<dyn std::error::Error>;
 type BoxResult = Result<(), BoxError>;
@@ -102,13 +179,39 @@ async fn main() -> BoxResult {
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 
+    SyscallFilter::protect_parent()?;
+    ResourceControl::log_mode();
+
     DATABASE.connect().await?;
     DATABASE.set_up_protip_table().await?;
+    DATABASE.set_up_run_table().await?;
+    DATABASE.set_up_submission_table().await?;
+    DATABASE.set_up_judgement_table().await?;
+
+    if let Some(pool) = WORKERS.as_ref() {
+        info!("Dispatching jobs to {} workers", pool.healthy_workers());
+        WorkerPool::start_health_checks(pool.clone());
+    }
+
+    let mut terminate = signal(SignalKind::terminate())?;
+
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+
+        info!("Shutting down, killing running programs");
+        ProcessGroup::kill_all();
+        std::process::exit(0);
+    });
 
     if let Err(why) = make_client().await?.start().await {
         error!("Client error: {:?}", why);
     }
 
+    ProcessGroup::kill_all();
+
     Ok(())
 }
 
@@ -125,7 +228,7 @@ fn make_framework(owners: HashSet<UserId>) -> StandardFramework {
     StandardFramework::new()
         .configure(|c| {
             c.with_whitespace(true)
-                .prefix("!")
+                .prefix(PREFIX)
                 .delimiters(vec![", ", ","])
                 .owners(owners)
         })
@@ -136,6 +239,17 @@ fn make_framework(owners: HashSet<UserId>) -> StandardFramework {
         .on_dispatch_error(dispatch_error)
         .help(&MY_HELP)
         .group(&BLACKBOX_GROUP)
+        .group(&INTERACTIVE_GROUP)
+        .group(&GENERATORS_GROUP)
+        .group(&HISTORY_GROUP)
+        .group(&BENCHMARKS_GROUP)
+        .group(&COMPLEXITY_GROUP)
+        .group(&SANITIZERS_GROUP)
+        .group(&SUBMISSIONS_GROUP)
+        .group(&JUDGING_GROUP)
+        .group(&RANKINGS_GROUP)
+        .group(&DEADLINES_GROUP)
+        .group(&CALIBRATION_GROUP)
         .group(&PROTIP_GROUP)
 }
 
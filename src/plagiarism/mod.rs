@@ -0,0 +1,3 @@
+pub mod similarity;
+pub mod tokenizer;
+pub mod winnowing;
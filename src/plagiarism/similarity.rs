@@ -0,0 +1,126 @@
+use crate::plagiarism::tokenizer::tokenize;
+use crate::plagiarism::winnowing::{fingerprints, Fingerprint};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq)]
+pub struct SimilarPair {
+    pub first: usize,
+    pub second: usize,
+    pub similarity: f64,
+    pub first_lines: (usize, usize),
+    pub second_lines: (usize, usize),
+}
+
+pub fn similar_pairs(sources: &[&str], threshold: f64) -> Vec<SimilarPair> {
+    let prints: Vec<Vec<Fingerprint>> = sources
+        .iter()
+        .map(|source| fingerprints(&tokenize(source)))
+        .collect();
+    let hashes: Vec<HashSet<u64>> = prints
+        .iter()
+        .map(|p| p.iter().map(|f| f.hash).collect())
+        .collect();
+
+    let mut pairs = Vec::new();
+
+    for first in 0..prints.len() {
+        for second in first + 1..prints.len() {
+            let smaller = hashes[first].len().min(hashes[second].len());
+            if smaller == 0 {
+                continue;
+            }
+
+            let shared: HashSet<u64> = hashes[first]
+                .intersection(&hashes[second])
+                .cloned()
+                .collect();
+            let similarity = shared.len() as f64 / smaller as f64;
+
+            if similarity >= threshold {
+                pairs.push(SimilarPair {
+                    first,
+                    second,
+                    similarity,
+                    first_lines: longest_match(&prints[first], &shared),
+                    second_lines: longest_match(&prints[second], &shared),
+                });
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+    pairs
+}
+
+fn longest_match(prints: &[Fingerprint], shared: &HashSet<u64>) -> (usize, usize) {
+    let mut best = (0, 0, 0);
+    let mut current: Option<(usize, usize, usize)> = None;
+
+    for print in prints {
+        if shared.contains(&print.hash) {
+            current = Some(match current {
+                Some((first, _, length)) => (first, print.last_line, length + 1),
+                None => (print.first_line, print.last_line, 1),
+            });
+
+            let (first, last, length) = current.unwrap();
+            if length > best.2 {
+                best = (first, last, length);
+            }
+        } else {
+            current = None;
+        }
+    }
+
+    (best.0, best.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plagiarism::similarity::similar_pairs;
+
+    const ORIGINAL: &str = "#include <cstdio>
+int main() {
+    int n, sum = 0;
+    scanf(\"%d\", &n);
+    for (int i = 0; i < n; i++) {
+        int x;
+        scanf(\"%d\", &x);
+        sum += x;
+    }
+    printf(\"%d\\n\", sum);
+}";
+
+    const RENAMED: &str = "#include <cstdio>
+// my own solution
+int main() {
+    int count, total = 0;
+    scanf(\"%d\", &count);
+    for (int j = 0; j < count; j++) {
+        int value; scanf(\"%d\", &value);
+        total += value;
+    }
+    printf(\"%d\\n\", total);
+}";
+
+    const DIFFERENT: &str = "#include <iostream>
+using namespace std;
+int main() {
+    string s;
+    while (cin >> s) {
+        if (s == \"end\") break;
+        cout << s.size() << endl;
+    }
+    return 0;
+}";
+
+    #[test]
+    fn should_find_renamed_copy() {
+        let pairs = similar_pairs(&[ORIGINAL, DIFFERENT, RENAMED], 0.8);
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].first, pairs[0].second), (0, 2));
+        assert!(pairs[0].similarity > 0.99);
+        assert_eq!(pairs[0].first_lines.0, 2);
+    }
+}
@@ -0,0 +1,154 @@
+const KEYWORDS: &[&str] = &[
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "false",
+    "float",
+    "for",
+    "if",
+    "int",
+    "long",
+    "namespace",
+    "new",
+    "nullptr",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "while",
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Token {
+    pub text: String,
+    pub line: usize,
+}
+
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).cloned();
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if (c == '/' && next == Some('/')) || c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(token("str", line));
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(token("num", line));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                tokens.push(token(&word, line));
+            } else {
+                tokens.push(token("id", line));
+            }
+        } else {
+            tokens.push(token(&c.to_string(), line));
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+fn token(text: &str, line: usize) -> Token {
+    Token {
+        text: text.to_string(),
+        line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plagiarism::tokenizer::tokenize;
+
+    fn texts(source: &str) -> Vec<String> {
+        tokenize(source).into_iter().map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn should_normalize_identifiers_and_literals() {
+        assert_eq!(
+            texts("int total = count + 42; // sum\n"),
+            vec!["int", "id", "=", "id", "+", "num", ";"]
+        );
+    }
+
+    #[test]
+    fn should_skip_comments_and_preprocessor() {
+        let source = "#include <cstdio>\n/* a\n b */ return \"x\";";
+        let tokens = tokenize(source);
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0].text, "return");
+        assert_eq!(tokens[0].line, 3);
+        assert_eq!(tokens[1].text, "str");
+    }
+}
@@ -0,0 +1,64 @@
+use crate::plagiarism::tokenizer::Token;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const K: usize = 5;
+const WINDOW: usize = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fingerprint {
+    pub hash: u64,
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+pub fn fingerprints(tokens: &[Token]) -> Vec<Fingerprint> {
+    if tokens.len() < K {
+        return Vec::new();
+    }
+
+    let grams: Vec<Fingerprint> = tokens
+        .windows(K)
+        .map(|gram| {
+            let mut hasher = DefaultHasher::new();
+            for token in gram {
+                token.text.hash(&mut hasher);
+            }
+
+            Fingerprint {
+                hash: hasher.finish(),
+                first_line: gram[0].line,
+                last_line: gram[K - 1].line,
+            }
+        })
+        .collect();
+
+    if grams.len() <= WINDOW {
+        return grams
+            .into_iter()
+            .min_by_key(|g| g.hash)
+            .into_iter()
+            .collect();
+    }
+
+    let mut selected: Vec<Fingerprint> = Vec::new();
+    let mut last_position = None;
+
+    for start in 0..=grams.len() - WINDOW {
+        let window = &grams[start..start + WINDOW];
+        let (offset, minimum) = window
+            .iter()
+            .enumerate()
+            .rev()
+            .min_by_key(|(_, g)| g.hash)
+            .unwrap();
+        let position = start + offset;
+
+        if last_position != Some(position) {
+            selected.push(minimum.clone());
+            last_position = Some(position);
+        }
+    }
+
+    selected
+}
@@ -0,0 +1 @@
+pub mod reply_tracker;
@@ -0,0 +1,89 @@
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, MessageId, UserId};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+const TRACKING_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Debug)]
+pub struct TrackedReply {
+    pub command_id: MessageId,
+    pub channel_id: ChannelId,
+    pub reply_id: MessageId,
+    pub user_id: UserId,
+    // Content of the command when it was last run.
+    pub content: String,
+    pub full_reply: Option<String>,
+    sent_at: Instant,
+}
+
+#[derive(Default)]
+pub struct ReplyTracker {
+    replies: Mutex<HashMap<MessageId, TrackedReply>>,
+}
+
+impl ReplyTracker {
+    pub fn new() -> Self {
+        ReplyTracker::default()
+    }
+
+    pub fn track(&self, command: &Message, reply: &Message, full_reply: Option<String>) {
+        let mut replies = self.replies.lock().unwrap();
+
+        replies.retain(|_, r| r.sent_at.elapsed() < TRACKING_WINDOW);
+        replies.insert(
+            command.id,
+            TrackedReply {
+                command_id: command.id,
+                channel_id: reply.channel_id,
+                reply_id: reply.id,
+                user_id: command.author.id,
+                content: command.content.clone(),
+                full_reply,
+                sent_at: Instant::now(),
+            },
+        );
+
+        debug!("Tracking {} replies", replies.len());
+    }
+
+    pub fn get(&self, command_id: MessageId) -> Option<TrackedReply> {
+        let replies = self.replies.lock().unwrap();
+
+        replies
+            .get(&command_id)
+            .filter(|r| r.sent_at.elapsed() < TRACKING_WINDOW)
+            .cloned()
+    }
+
+    pub fn find_by_reply(&self, reply_id: MessageId) -> Option<TrackedReply> {
+        let replies = self.replies.lock().unwrap();
+
+        replies
+            .values()
+            .find(|r| r.reply_id == reply_id && r.sent_at.elapsed() < TRACKING_WINDOW)
+            .cloned()
+    }
+
+    pub fn set_full_reply(&self, command_id: MessageId, full_reply: Option<String>) {
+        let mut replies = self.replies.lock().unwrap();
+
+        if let Some(reply) = replies.get_mut(&command_id) {
+            reply.full_reply = full_reply;
+        }
+    }
+
+    pub fn set_content(&self, command_id: MessageId, content: &str) {
+        let mut replies = self.replies.lock().unwrap();
+
+        if let Some(reply) = replies.get_mut(&command_id) {
+            reply.content = content.to_string();
+        }
+    }
+
+    pub fn untrack(&self, command_id: MessageId) {
+        self.replies.lock().unwrap().remove(&command_id);
+    }
+}
@@ -1,2 +0,0 @@
-pub mod binary_runner;
-pub mod runner_error;